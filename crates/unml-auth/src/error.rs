@@ -8,9 +8,72 @@ pub enum Error {
     #[error("Token expired")]
     TokenExpired,
 
+    #[error("The login code has expired. Start the login again to get a new code")]
+    DeviceCodeExpired,
+
+    #[error("Login cancelled")]
+    Cancelled,
+
+    #[error(
+        "The sign-in request was declined. Add the account again and approve the request to continue"
+    )]
+    Declined,
+
     #[error("Invalid credentials")]
     InvalidCredentials,
 
+    #[error("Invalid username \"{0}\": use 3-16 characters of letters, digits and underscores")]
+    InvalidUsername(String),
+
+    #[error("Invalid UUID: {0}")]
+    InvalidUuid(String),
+
+    #[error(
+        "This Microsoft account has no Xbox account. Sign in at https://www.xbox.com/live to create one, then try again"
+    )]
+    NoXboxAccount,
+
+    #[error("Xbox Live is not available in your country or region")]
+    XboxRegionBanned,
+
+    #[error(
+        "This account needs adult verification. Visit https://account.xbox.com to verify your age"
+    )]
+    AdultVerificationRequired,
+
+    #[error(
+        "This is a child account. An adult must add it to a Microsoft family at https://account.microsoft.com/family before it can play"
+    )]
+    ChildAccount,
+
+    #[error("Xbox Live authorization failed (XErr {code})")]
+    Xsts { code: u64, redirect: Option<String> },
+
+    #[error(
+        "This account does not own Minecraft: Java Edition. Buy the game or subscribe to PC Game Pass, then try again"
+    )]
+    NotOwned,
+
+    #[error(
+        "This account has no Minecraft profile yet. Launch the official launcher once or visit https://www.minecraft.net to choose a profile name"
+    )]
+    ProfileMissing,
+
+    #[error("This account has several profiles. Choose one to continue")]
+    ProfileNotSelected,
+
+    #[error("Invalid skin: {0}")]
+    InvalidSkin(String),
+
+    #[error("Invalid cape: {0}")]
+    InvalidCape(String),
+
+    #[error("Failed to update profile: {0}")]
+    ProfileUpdate(String),
+
+    #[error(transparent)]
+    Io(#[from] unml_core::IoError),
+
     #[error(transparent)]
     Http(#[from] unml_core::HttpError),
 
@@ -18,4 +81,23 @@ pub enum Error {
     Json(#[from] unml_core::JsonError),
 }
 
+impl Error {
+    /// 将 XSTS 返回的 `XErr` 错误码映射为对应的错误
+    pub fn from_xerr(code: u64, redirect: Option<String>) -> Self {
+        match code {
+            2_148_916_233 => Self::NoXboxAccount,
+            2_148_916_235 => Self::XboxRegionBanned,
+            2_148_916_236 | 2_148_916_237 => Self::AdultVerificationRequired,
+            2_148_916_238 => Self::ChildAccount,
+            _ => Self::Xsts { code, redirect },
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(unml_core::IoError(e))
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -2,8 +2,32 @@ mod error;
 mod microsoft;
 mod oauth;
 mod offline;
+mod profile;
+mod skin;
+mod texture_server;
+mod yggdrasil;
+
+use std::sync::OnceLock;
 
 pub use error::{Error, Result};
-pub use microsoft::MicrosoftAuthProvider;
-pub use oauth::DeviceCodeResponse;
+pub use microsoft::{LoginEvent, MicrosoftAuthProvider};
+pub use oauth::{DeviceCodeResponse, MinecraftProfile};
 pub use offline::OfflineAuthProvider;
+use reqwest::Client;
+pub use skin::{
+    Cape, DefaultSkin, Skin, SkinCache, SkinVariant, TextureState, render_avatar, validate_cape,
+    validate_skin,
+};
+pub use texture_server::LocalTextureServer;
+pub use yggdrasil::{ProfileSelector, YggdrasilAuthProvider, YggdrasilProfile};
+
+static HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
+
+pub(crate) fn http_client() -> &'static Client {
+    HTTP_CLIENT.get_or_init(|| {
+        Client::builder()
+            .user_agent("UNML/0.1.0")
+            .build()
+            .expect("Failed to create HTTP client")
+    })
+}
@@ -1,48 +1,131 @@
-use std::sync::OnceLock;
+use std::future::Future;
 
 use async_trait::async_trait;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
 use unml_core::{Account, AccountType, AuthProvider, Credentials};
 
-use crate::oauth::{self, DeviceCodeResponse, TokenResponse};
-use crate::{Error, Result};
-
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-
-fn http_client() -> &'static reqwest::Client {
-    HTTP_CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
-            .user_agent("UNML/0.1.0")
-            .build()
-            .expect("Failed to create HTTP client")
-    })
+use crate::oauth::{self, MinecraftProfile, TokenResponse};
+use crate::skin::{Cape, SkinVariant, validate_skin};
+use crate::{Error, Result, http_client, profile};
+
+/// 微软登录进度事件
+#[derive(Debug, Clone)]
+pub enum LoginEvent {
+    /// 等待用户访问 `verification_uri` 并输入代码
+    AwaitingUserCode {
+        user_code: String,
+        verification_uri: String,
+        /// 代码有效期（秒）
+        expires_in: u32,
+    },
+    /// Xbox Live 认证
+    ExchangingXboxToken,
+    /// XSTS 认证
+    AuthorizingXsts,
+    /// Minecraft 认证
+    AuthenticatingMinecraft,
+    /// 检查游戏所有权
+    CheckingOwnership,
+    /// 获取 Minecraft Profile
+    FetchingProfile,
 }
 
-/// 微软登录回调，用于获取设备码信息
-pub type DeviceCodeCallback = Box<dyn Fn(&DeviceCodeResponse) + Send + Sync>;
-
 pub struct MicrosoftAuthProvider {
-    device_code_callback: Option<DeviceCodeCallback>,
+    events: Option<UnboundedSender<LoginEvent>>,
+    cancel: CancellationToken,
 }
 
 impl MicrosoftAuthProvider {
     pub fn new() -> Self {
         Self {
-            device_code_callback: None,
+            events: None,
+            cancel: CancellationToken::new(),
         }
     }
 
-    /// 设置设备码回调（用于通知用户访问 URL 并输入代码）
-    pub fn with_device_code_callback(mut self, callback: DeviceCodeCallback) -> Self {
-        self.device_code_callback = Some(callback);
+    /// 设置登录进度事件的接收端
+    pub fn with_events(mut self, events: UnboundedSender<LoginEvent>) -> Self {
+        self.events = Some(events);
         self
     }
 
+    /// 设置取消令牌，令牌被取消后登录流程会以 [`Error::Cancelled`] 结束
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    fn emit(&self, event: LoginEvent) {
+        if let Some(events) = &self.events {
+            // 接收端已关闭说明界面不再关心进度，忽略即可
+            let _ = events.send(event);
+        }
+    }
+
+    /// 执行登录中的一步，同时响应取消
+    async fn step<T>(
+        &self,
+        event: LoginEvent,
+        future: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        self.emit(event);
+
+        tokio::select! {
+            biased;
+            () = self.cancel.cancelled() => Err(Error::Cancelled),
+            result = future => result,
+        }
+    }
+
+    /// 获取账号的 Minecraft Profile（包含皮肤与披风）
+    pub async fn fetch_profile(&self, account: &Account) -> Result<MinecraftProfile> {
+        oauth::get_minecraft_profile(http_client(), &account.access_token).await
+    }
+
+    /// 上传皮肤，PNG 必须为 64x64 或 64x32
+    pub async fn upload_skin(
+        &self,
+        account: &Account,
+        png: Vec<u8>,
+        variant: SkinVariant,
+    ) -> Result<MinecraftProfile> {
+        validate_skin(&png)?;
+
+        profile::upload_skin(http_client(), &account.access_token, png, variant).await
+    }
+
+    /// 重置为默认皮肤
+    pub async fn reset_skin(&self, account: &Account) -> Result<MinecraftProfile> {
+        profile::reset_skin(http_client(), &account.access_token).await
+    }
+
+    /// 列出账号拥有的所有披风
+    pub async fn list_capes(&self, account: &Account) -> Result<Vec<Cape>> {
+        Ok(self.fetch_profile(account).await?.capes)
+    }
+
+    /// 佩戴披风
+    pub async fn equip_cape(&self, account: &Account, cape_id: &str) -> Result<MinecraftProfile> {
+        profile::equip_cape(http_client(), &account.access_token, cape_id).await
+    }
+
+    /// 隐藏当前披风
+    pub async fn hide_cape(&self, account: &Account) -> Result<MinecraftProfile> {
+        profile::hide_cape(http_client(), &account.access_token).await
+    }
+
     /// 用 MS Token 换取 Minecraft Account
     async fn exchange_token_for_account(&self, ms_token: TokenResponse) -> Result<Account> {
         let client = http_client();
 
         // 1. Xbox Live 认证
-        let xbox_response = oauth::authenticate_xbox_live(client, &ms_token.access_token).await?;
+        let xbox_response = self
+            .step(
+                LoginEvent::ExchangingXboxToken,
+                oauth::authenticate_xbox_live(client, &ms_token.access_token),
+            )
+            .await?;
         let user_hash = xbox_response
             .display_claims
             .xui
@@ -52,14 +135,39 @@ impl MicrosoftAuthProvider {
             .uhs;
 
         // 2. XSTS 认证
-        let xsts_response = oauth::authenticate_xsts(client, &xbox_response.token).await?;
+        let xsts_response = self
+            .step(
+                LoginEvent::AuthorizingXsts,
+                oauth::authenticate_xsts(client, &xbox_response.token),
+            )
+            .await?;
 
         // 3. Minecraft 认证
-        let mc_auth =
-            oauth::authenticate_minecraft(client, &user_hash, &xsts_response.token).await?;
+        let mc_auth = self
+            .step(
+                LoginEvent::AuthenticatingMinecraft,
+                oauth::authenticate_minecraft(client, &user_hash, &xsts_response.token),
+            )
+            .await?;
+
+        // 4. 检查游戏所有权
+        let entitlements = self
+            .step(
+                LoginEvent::CheckingOwnership,
+                oauth::get_entitlements(client, &mc_auth.access_token),
+            )
+            .await?;
+        if !entitlements.owns_java_edition() {
+            return Err(Error::NotOwned);
+        }
 
-        // 4. 获取 Minecraft Profile
-        let profile = oauth::get_minecraft_profile(client, &mc_auth.access_token).await?;
+        // 5. 获取 Minecraft Profile
+        let profile = self
+            .step(
+                LoginEvent::FetchingProfile,
+                oauth::get_minecraft_profile(client, &mc_auth.access_token),
+            )
+            .await?;
 
         Ok(Account {
             username: profile.name,
@@ -67,6 +175,7 @@ impl MicrosoftAuthProvider {
             access_token: mc_auth.access_token,
             refresh_token: Some(ms_token.refresh_token),
             account_type: AccountType::Microsoft,
+            local_textures: None,
         })
     }
 
@@ -75,22 +184,21 @@ impl MicrosoftAuthProvider {
         let client = http_client();
 
         // 1. 获取设备码
-        let device_code = oauth::request_device_code(client).await?;
+        let device_code = tokio::select! {
+            biased;
+            () = self.cancel.cancelled() => return Err(Error::Cancelled),
+            result = oauth::request_device_code(client) => result?,
+        };
 
         // 通知用户
-        if let Some(ref callback) = self.device_code_callback {
-            callback(&device_code);
-        } else {
-            // 默认打印到控制台
-            eprintln!(
-                "Please visit {} and enter code: {}",
-                device_code.verification_uri, device_code.user_code
-            );
-        }
+        self.emit(LoginEvent::AwaitingUserCode {
+            user_code: device_code.user_code.clone(),
+            verification_uri: device_code.verification_uri.clone(),
+            expires_in: device_code.expires_in,
+        });
 
         // 2. 轮询等待用户授权
-        let ms_token =
-            oauth::poll_for_token(client, &device_code.device_code, device_code.interval).await?;
+        let ms_token = oauth::poll_for_token(client, &device_code, &self.cancel).await?;
 
         // 3. 换取 Minecraft Account
         self.exchange_token_for_account(ms_token).await
@@ -1,12 +1,17 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 
+use crate::skin::{Cape, Skin, TextureState};
 use crate::{Error, Result};
 
 /// 公开的 Azure 应用 Client ID（用于个人账户）
 pub const CLIENT_ID: &str = "00000000402b5328";
 
 /// Device Code 响应
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct DeviceCodeResponse {
     pub device_code: String,
     pub user_code: String,
@@ -29,6 +34,16 @@ struct TokenErrorResponse {
     error: String,
 }
 
+/// XSTS 错误响应
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XstsErrorResponse {
+    #[serde(rename = "XErr")]
+    xerr: u64,
+    #[serde(default)]
+    redirect: Option<String>,
+}
+
 /// Xbox Live 认证响应
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -54,11 +69,60 @@ pub struct MinecraftAuthResponse {
     pub expires_in: u32,
 }
 
-/// Minecraft Profile 响应
+/// 游戏所有权响应
+#[derive(Debug, Deserialize)]
+pub struct EntitlementsResponse {
+    #[serde(default)]
+    pub items: Vec<EntitlementItem>,
+}
+
 #[derive(Debug, Deserialize)]
+pub struct EntitlementItem {
+    pub name: String,
+}
+
+impl EntitlementsResponse {
+    /// 可以游玩 Java 版的权益（包括 Xbox Game Pass）
+    const JAVA_EDITION_ITEMS: &[&str] = &[
+        "product_minecraft",
+        "game_minecraft",
+        "product_game_pass_pc",
+        "product_game_pass_ultimate",
+    ];
+
+    /// 是否拥有 Minecraft: Java Edition
+    pub fn owns_java_edition(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| Self::JAVA_EDITION_ITEMS.contains(&item.name.as_str()))
+    }
+}
+
+/// Minecraft Profile 响应
+#[derive(Debug, Clone, Deserialize)]
 pub struct MinecraftProfile {
     pub id: String,
     pub name: String,
+    #[serde(default)]
+    pub skins: Vec<Skin>,
+    #[serde(default)]
+    pub capes: Vec<Cape>,
+}
+
+impl MinecraftProfile {
+    /// 当前使用的皮肤
+    pub fn active_skin(&self) -> Option<&Skin> {
+        self.skins
+            .iter()
+            .find(|skin| skin.state == TextureState::Active)
+    }
+
+    /// 当前佩戴的披风
+    pub fn active_cape(&self) -> Option<&Cape> {
+        self.capes
+            .iter()
+            .find(|cape| cape.state == TextureState::Active)
+    }
 }
 
 /// 请求设备码
@@ -79,21 +143,32 @@ pub async fn request_device_code(client: &reqwest::Client) -> Result<DeviceCodeR
         .map_err(|e| Error::AuthFailed(format!("Failed to parse device code response: {e}")))
 }
 
-/// 轮询等待用户授权
+/// 轮询等待用户授权，直到授权成功、设备码过期或被取消
 pub async fn poll_for_token(
     client: &reqwest::Client,
-    device_code: &str,
-    interval: u32,
+    device_code: &DeviceCodeResponse,
+    cancel: &CancellationToken,
 ) -> Result<TokenResponse> {
+    let deadline = Instant::now() + Duration::from_secs(u64::from(device_code.expires_in));
+    let mut interval = Duration::from_secs(u64::from(device_code.interval.max(1)));
+
     loop {
-        tokio::time::sleep(std::time::Duration::from_secs(u64::from(interval))).await;
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => return Err(Error::Cancelled),
+            () = tokio::time::sleep(interval) => {}
+        }
+
+        if Instant::now() >= deadline {
+            return Err(Error::DeviceCodeExpired);
+        }
 
         let response = client
             .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
             .form(&[
                 ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
                 ("client_id", CLIENT_ID),
-                ("device_code", device_code),
+                ("device_code", device_code.device_code.as_str()),
             ])
             .send()
             .await
@@ -115,11 +190,10 @@ pub async fn poll_for_token(
 
         match error.error.as_str() {
             "authorization_pending" => continue,
-            "slow_down" => {
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                continue;
-            }
-            "expired_token" => return Err(Error::TokenExpired),
+            // RFC 8628：收到 slow_down 后轮询间隔需要增加 5 秒
+            "slow_down" => interval += Duration::from_secs(5),
+            "expired_token" => return Err(Error::DeviceCodeExpired),
+            "authorization_declined" => return Err(Error::Declined),
             _ => return Err(Error::AuthFailed(format!("Auth error: {}", error.error))),
         }
     }
@@ -235,7 +309,11 @@ pub async fn authenticate_xsts(
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
-        return Err(Error::AuthFailed(format!("XSTS auth failed: {body}")));
+
+        return Err(match serde_json::from_str::<XstsErrorResponse>(&body) {
+            Ok(error) => Error::from_xerr(error.xerr, error.redirect),
+            Err(_) => Error::AuthFailed(format!("XSTS auth failed: {body}")),
+        });
     }
 
     response
@@ -278,6 +356,31 @@ pub async fn authenticate_minecraft(
         .map_err(|e| Error::AuthFailed(format!("Failed to parse Minecraft auth response: {e}")))
 }
 
+/// 查询游戏所有权
+pub async fn get_entitlements(
+    client: &reqwest::Client,
+    mc_access_token: &str,
+) -> Result<EntitlementsResponse> {
+    let response = client
+        .get("https://api.minecraftservices.com/entitlements/mcstore")
+        .bearer_auth(mc_access_token)
+        .send()
+        .await
+        .map_err(|e| Error::AuthFailed(format!("Failed to get entitlements: {e}")))?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::AuthFailed(format!(
+            "Failed to get entitlements: {body}"
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| Error::AuthFailed(format!("Failed to parse entitlements: {e}")))
+}
+
 /// 获取 Minecraft Profile
 pub async fn get_minecraft_profile(
     client: &reqwest::Client,
@@ -290,6 +393,11 @@ pub async fn get_minecraft_profile(
         .await
         .map_err(|e| Error::AuthFailed(format!("Failed to get profile: {e}")))?;
 
+    // 账号已购买游戏但尚未创建角色名
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(Error::ProfileMissing);
+    }
+
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
         return Err(Error::AuthFailed(format!(
@@ -1,7 +1,10 @@
+use std::path::Path;
+
 use async_trait::async_trait;
-use unml_core::{Account, AccountType, AuthProvider, Credentials};
+use unml_core::{Account, AccountType, AuthProvider, Credentials, LocalTextures};
 use uuid::Uuid;
 
+use crate::skin::{validate_cape, validate_skin};
 use crate::{Error, Result};
 
 pub struct OfflineAuthProvider;
@@ -15,6 +18,43 @@ impl OfflineAuthProvider {
         let name = format!("OfflinePlayer:{username}");
         Uuid::new_v3(&Uuid::NAMESPACE_DNS, name.as_bytes()).to_string()
     }
+
+    /// 检查用户名是否合法：3-16 个字符，只能包含字母、数字和下划线
+    pub fn validate_username(username: &str) -> Result<()> {
+        let valid_length = (3..=16).contains(&username.len());
+        let valid_chars = username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if valid_length && valid_chars {
+            Ok(())
+        } else {
+            Err(Error::InvalidUsername(username.to_owned()))
+        }
+    }
+
+    /// 设置本地皮肤与披风
+    pub async fn set_local_textures(
+        account: &mut Account,
+        skin: Option<&Path>,
+        slim: bool,
+        cape: Option<&Path>,
+    ) -> Result<()> {
+        if let Some(skin) = skin {
+            validate_skin(&tokio::fs::read(skin).await?)?;
+        }
+        if let Some(cape) = cape {
+            validate_cape(&tokio::fs::read(cape).await?)?;
+        }
+
+        account.local_textures = (skin.is_some() || cape.is_some()).then(|| LocalTextures {
+            skin: skin.map(Path::to_path_buf),
+            slim,
+            cape: cape.map(Path::to_path_buf),
+        });
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -23,14 +63,24 @@ impl AuthProvider for OfflineAuthProvider {
 
     async fn login(&self, credentials: Credentials) -> Result<Account> {
         match credentials {
-            Credentials::Offline { username } => {
-                let uuid = Self::offline_uuid(&username);
+            Credentials::Offline { username, uuid } => {
+                Self::validate_username(&username)?;
+
+                // 固定 UUID 可以在改名后保留存档数据
+                let uuid = match uuid {
+                    Some(uuid) => Uuid::parse_str(&uuid)
+                        .map_err(|_| Error::InvalidUuid(uuid))?
+                        .to_string(),
+                    None => Self::offline_uuid(&username),
+                };
+
                 Ok(Account {
                     username,
                     uuid,
                     access_token: String::new(),
                     refresh_token: None,
                     account_type: AccountType::Offline,
+                    local_textures: None,
                 })
             }
             _ => Err(Error::AuthFailed(
@@ -0,0 +1,101 @@
+use serde::Serialize;
+
+use crate::oauth::MinecraftProfile;
+use crate::skin::SkinVariant;
+use crate::{Error, Result};
+
+const PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
+
+/// 上传皮肤
+pub async fn upload_skin(
+    client: &reqwest::Client,
+    mc_access_token: &str,
+    png: Vec<u8>,
+    variant: SkinVariant,
+) -> Result<MinecraftProfile> {
+    let variant = match variant {
+        SkinVariant::Classic => "classic",
+        SkinVariant::Slim => "slim",
+    };
+
+    let file = reqwest::multipart::Part::bytes(png)
+        .file_name("skin.png")
+        .mime_str("image/png")
+        .map_err(|e| Error::ProfileUpdate(e.to_string()))?;
+    let form = reqwest::multipart::Form::new()
+        .text("variant", variant)
+        .part("file", file);
+
+    let request = client
+        .post(format!("{PROFILE_URL}/skins"))
+        .bearer_auth(mc_access_token)
+        .multipart(form);
+
+    send(request, "upload skin").await
+}
+
+/// 重置为默认皮肤
+pub async fn reset_skin(
+    client: &reqwest::Client,
+    mc_access_token: &str,
+) -> Result<MinecraftProfile> {
+    let request = client
+        .delete(format!("{PROFILE_URL}/skins/active"))
+        .bearer_auth(mc_access_token);
+
+    send(request, "reset skin").await
+}
+
+/// 佩戴披风
+pub async fn equip_cape(
+    client: &reqwest::Client,
+    mc_access_token: &str,
+    cape_id: &str,
+) -> Result<MinecraftProfile> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct EquipCapeRequest<'a> {
+        cape_id: &'a str,
+    }
+
+    let request = client
+        .put(format!("{PROFILE_URL}/capes/active"))
+        .bearer_auth(mc_access_token)
+        .json(&EquipCapeRequest { cape_id });
+
+    send(request, "equip cape").await
+}
+
+/// 隐藏披风
+pub async fn hide_cape(
+    client: &reqwest::Client,
+    mc_access_token: &str,
+) -> Result<MinecraftProfile> {
+    let request = client
+        .delete(format!("{PROFILE_URL}/capes/active"))
+        .bearer_auth(mc_access_token);
+
+    send(request, "hide cape").await
+}
+
+async fn send(request: reqwest::RequestBuilder, action: &str) -> Result<MinecraftProfile> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| Error::ProfileUpdate(format!("Failed to {action}: {e}")))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(Error::TokenExpired);
+    }
+
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::ProfileUpdate(format!("Failed to {action}: {body}")));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| Error::ProfileUpdate(format!("Failed to parse profile: {e}")))
+}
@@ -0,0 +1,247 @@
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use image::imageops::{self, FilterType};
+use image::{ImageFormat, ImageReader, RgbaImage};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{Error, Result, http_client};
+
+/// 皮肤模型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SkinVariant {
+    /// 经典（Steve，4 像素手臂）
+    Classic,
+    /// 纤细（Alex，3 像素手臂）
+    Slim,
+}
+
+/// 材质状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TextureState {
+    Active,
+    Inactive,
+}
+
+/// Profile 中的皮肤
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skin {
+    pub id: String,
+    pub state: TextureState,
+    pub url: String,
+    pub variant: SkinVariant,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+/// Profile 中的披风
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cape {
+    pub id: String,
+    pub state: TextureState,
+    pub url: String,
+    pub alias: String,
+}
+
+/// 默认皮肤（离线账号或没有自定义皮肤时使用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultSkin {
+    Steve,
+    Alex,
+}
+
+impl DefaultSkin {
+    /// 与原版一致：根据 UUID 的 Java `hashCode` 奇偶性选择 Steve 或 Alex
+    pub fn for_uuid(uuid: &str) -> Self {
+        let Ok(uuid) = Uuid::parse_str(uuid) else {
+            return Self::Steve;
+        };
+
+        let (most, least) = uuid.as_u64_pair();
+        let hilo = most ^ least;
+        let hash = (hilo >> 32) ^ (hilo & 0xFFFF_FFFF);
+
+        if hash & 1 == 0 {
+            Self::Steve
+        } else {
+            Self::Alex
+        }
+    }
+
+    pub fn url(self) -> &'static str {
+        match self {
+            Self::Steve => {
+                "https://textures.minecraft.net/texture/1a4af718455d4aab528e7a61f86fa25e6a369d1768dcb13f7df319a713eb810b"
+            }
+            Self::Alex => {
+                "https://textures.minecraft.net/texture/3b60a1f6d562f52aaebbf1434f1de147933a3affe0e764fa49ea057536623cd3"
+            }
+        }
+    }
+
+    pub fn variant(self) -> SkinVariant {
+        match self {
+            Self::Steve => SkinVariant::Classic,
+            Self::Alex => SkinVariant::Slim,
+        }
+    }
+}
+
+/// 材质磁盘缓存，以材质哈希为键
+pub struct SkinCache {
+    root: PathBuf,
+}
+
+impl SkinCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// 材质 URL 的最后一段即为材质哈希
+    pub fn texture_hash(url: &str) -> &str {
+        url.rsplit('/').next().unwrap_or(url)
+    }
+
+    pub fn texture_path(&self, hash: &str) -> PathBuf {
+        self.root.join("textures").join(format!("{hash}.png"))
+    }
+
+    fn avatar_path(&self, hash: &str, size: u32) -> PathBuf {
+        self.root.join("avatars").join(format!("{hash}-{size}.png"))
+    }
+
+    /// 获取材质文件，不存在时下载
+    pub async fn fetch(&self, url: &str) -> Result<PathBuf> {
+        let path = self.texture_path(Self::texture_hash(url));
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(path);
+        }
+
+        let bytes = http_client()
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| unml_core::HttpError(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+        write_atomic(&path, &bytes).await?;
+
+        Ok(path)
+    }
+
+    /// 获取账号的皮肤文件，没有皮肤时回退到默认皮肤
+    pub async fn fetch_skin(&self, uuid: &str, skin: Option<&Skin>) -> Result<PathBuf> {
+        match skin {
+            Some(skin) => self.fetch(&skin.url).await,
+            None => self.fetch(DefaultSkin::for_uuid(uuid).url()).await,
+        }
+    }
+
+    /// 获取指定尺寸的头像，渲染结果同样会被缓存
+    pub async fn avatar(&self, skin_url: &str, size: u32) -> Result<PathBuf> {
+        let hash = Self::texture_hash(skin_url);
+        let path = self.avatar_path(hash, size);
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(path);
+        }
+
+        let skin = tokio::fs::read(self.fetch(skin_url).await?).await?;
+        let avatar = render_avatar(&skin, size)?;
+        write_atomic(&path, &avatar).await?;
+
+        Ok(path)
+    }
+
+    /// 渲染本地皮肤文件的头像，以文件内容的 SHA-256 为缓存键
+    pub async fn local_avatar(&self, skin: &Path, size: u32) -> Result<PathBuf> {
+        let skin = tokio::fs::read(skin).await?;
+        let path = self.avatar_path(&format!("{:x}", Sha256::digest(&skin)), size);
+        if !tokio::fs::try_exists(&path).await? {
+            write_atomic(&path, &render_avatar(&skin, size)?).await?;
+        }
+
+        Ok(path)
+    }
+}
+
+/// 检查皮肤文件是否为有效的 64x64 或 64x32 PNG，返回 (宽, 高)
+pub fn validate_skin(skin_png: &[u8]) -> Result<(u32, u32)> {
+    let (width, height) = ImageReader::with_format(Cursor::new(skin_png), ImageFormat::Png)
+        .into_dimensions()
+        .map_err(|e| Error::InvalidSkin(e.to_string()))?;
+
+    if width != 64 || (height != 64 && height != 32) {
+        return Err(Error::InvalidSkin(format!(
+            "skin must be 64x64 or 64x32, got {width}x{height}"
+        )));
+    }
+
+    Ok((width, height))
+}
+
+/// 检查披风文件是否为有效的 PNG：64x32 及其整数倍，或旧版的 22x17，返回 (宽,
+/// 高)
+pub fn validate_cape(cape_png: &[u8]) -> Result<(u32, u32)> {
+    let (width, height) = ImageReader::with_format(Cursor::new(cape_png), ImageFormat::Png)
+        .into_dimensions()
+        .map_err(|e| Error::InvalidCape(e.to_string()))?;
+
+    let standard = width > 0 && width % 64 == 0 && width == height * 2;
+    if !standard && (width, height) != (22, 17) {
+        return Err(Error::InvalidCape(format!(
+            "cape must be 64x32 or a multiple of it, got {width}x{height}"
+        )));
+    }
+
+    Ok((width, height))
+}
+
+/// 从皮肤渲染 2D 头像（脸部加帽子层），返回 PNG 数据
+pub fn render_avatar(skin_png: &[u8], size: u32) -> Result<Vec<u8>> {
+    let (_, height) = validate_skin(skin_png)?;
+
+    let skin = image::load_from_memory_with_format(skin_png, ImageFormat::Png)
+        .map_err(|e| Error::InvalidSkin(e.to_string()))?
+        .to_rgba8();
+    let mut head: RgbaImage = imageops::crop_imm(&skin, 8, 8, 8, 8).to_image();
+    let hat = imageops::crop_imm(&skin, 40, 8, 8, 8).to_image();
+
+    // 旧版 64x32 皮肤的帽子层经常是完全不透明的，原版会忽略这种帽子层
+    let legacy_opaque_hat = height == 32 && hat.pixels().all(|p| p[3] == 255);
+    if !legacy_opaque_hat {
+        imageops::overlay(&mut head, &hat, 0, 0);
+    }
+
+    let head = imageops::resize(&head, size.max(1), size.max(1), FilterType::Nearest);
+
+    let mut png = Vec::new();
+    head.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| Error::InvalidSkin(e.to_string()))?;
+
+    Ok(png)
+}
+
+async fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    // 同一文件可能被并发写入，每次使用唯一的临时文件
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", Uuid::new_v4().simple()));
+    let tmp = PathBuf::from(tmp);
+    tokio::fs::write(&tmp, bytes).await?;
+    if let Err(e) = tokio::fs::rename(&tmp, path).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use unml_core::{Account, AccountType};
+
+use crate::Result;
+use crate::skin::{validate_cape, validate_skin};
+
+/// 请求体上限，本地服务只会收到很小的 JSON 请求
+const MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+const JSON: &str = "application/json; charset=utf-8";
+
+struct Texture {
+    hash: String,
+    data: Vec<u8>,
+}
+
+impl Texture {
+    async fn load(path: &std::path::Path) -> Result<Self> {
+        let data = tokio::fs::read(path).await?;
+        let hash = format!("{:x}", Sha256::digest(&data));
+
+        Ok(Self { hash, data })
+    }
+}
+
+struct State {
+    api_root: String,
+    /// 不带连字符的 UUID
+    profile_id: String,
+    name: String,
+    skin: Option<Texture>,
+    slim: bool,
+    cape: Option<Texture>,
+}
+
+/// 本地 Yggdrasil 兼容材质服务
+///
+/// 离线账号通过 authlib-injector 指向该服务，单人游戏中即可显示本地皮肤与披风。
+/// 服务随该对象一起销毁，需要在游戏运行期间一直持有。
+pub struct LocalTextureServer {
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl LocalTextureServer {
+    pub async fn start(account: &Account) -> Result<Self> {
+        let textures = account.local_textures.clone().unwrap_or_default();
+
+        let skin = match &textures.skin {
+            Some(path) => {
+                let skin = Texture::load(path).await?;
+                validate_skin(&skin.data)?;
+                Some(skin)
+            }
+            None => None,
+        };
+        let cape = match &textures.cape {
+            Some(path) => {
+                let cape = Texture::load(path).await?;
+                validate_cape(&cape.data)?;
+                Some(cape)
+            }
+            None => None,
+        };
+
+        let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).await?;
+        let port = listener.local_addr()?.port();
+
+        let state = Arc::new(State {
+            api_root: format!("http://127.0.0.1:{port}"),
+            profile_id: account.uuid.replace('-', ""),
+            name: account.username.clone(),
+            skin,
+            slim: textures.slim,
+            cape,
+        });
+
+        let task = tokio::spawn({
+            let state = Arc::clone(&state);
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let state = Arc::clone(&state);
+                    tokio::spawn(async move {
+                        let _ = handle_connection(stream, &state).await;
+                    });
+                }
+            }
+        });
+
+        Ok(Self { state, task })
+    }
+
+    pub fn api_root(&self) -> &str {
+        &self.state.api_root
+    }
+
+    /// 生成用于启动的账号：把离线账号伪装成指向本地服务的 Yggdrasil 账号
+    pub fn launch_account(&self, account: &Account) -> Account {
+        let mut account = account.clone();
+        account.account_type = AccountType::Yggdrasil {
+            api_root: self.state.api_root.clone(),
+            client_token: String::new(),
+            metadata: Some(STANDARD.encode(metadata().to_string())),
+        };
+
+        account
+    }
+}
+
+impl Drop for LocalTextureServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle_connection(stream: TcpStream, state: &State) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let target = parts.next().unwrap_or_default().to_owned();
+
+    // 读取并丢弃请求头与请求体
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    if content_length > MAX_BODY_SIZE {
+        let mut stream = reader.into_inner();
+        return respond(&mut stream, "413 Payload Too Large", JSON, &[]).await;
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    let mut stream = reader.into_inner();
+    let path = target.split('?').next().unwrap_or_default();
+    let (status, content_type, body) = route(state, &method, path);
+    respond(&mut stream, status, content_type, &body).await
+}
+
+async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
+
+fn route(state: &State, method: &str, path: &str) -> (&'static str, &'static str, Vec<u8>) {
+    match (method, path) {
+        ("GET", "/" | "") => ("200 OK", JSON, metadata().to_string().into_bytes()),
+        ("GET", "/sessionserver/session/minecraft/hasJoined") => {
+            ("200 OK", JSON, profile(state).to_string().into_bytes())
+        }
+        ("POST", "/api/profiles/minecraft") => {
+            let profiles = serde_json::json!([{ "id": state.profile_id, "name": state.name }]);
+            ("200 OK", JSON, profiles.to_string().into_bytes())
+        }
+        ("GET", _) if path.starts_with("/sessionserver/session/minecraft/profile/") => {
+            let id = path.rsplit('/').next().unwrap_or_default().replace('-', "");
+            if id == state.profile_id {
+                ("200 OK", JSON, profile(state).to_string().into_bytes())
+            } else {
+                ("204 No Content", JSON, Vec::new())
+            }
+        }
+        ("GET", _) if path.starts_with("/textures/") => {
+            let hash = path.rsplit('/').next().unwrap_or_default();
+            [&state.skin, &state.cape]
+                .into_iter()
+                .flatten()
+                .find(|texture| texture.hash == hash)
+                .map_or(("404 Not Found", JSON, Vec::new()), |texture| {
+                    ("200 OK", "image/png", texture.data.clone())
+                })
+        }
+        // join、validate 等请求在本地没有意义，直接视为成功
+        ("POST", _) => ("204 No Content", JSON, Vec::new()),
+        _ => ("404 Not Found", JSON, Vec::new()),
+    }
+}
+
+fn metadata() -> serde_json::Value {
+    serde_json::json!({
+        "meta": {
+            "serverName": "UNML Offline",
+            "implementationName": "unml",
+            "implementationVersion": env!("CARGO_PKG_VERSION"),
+            "feature.no_mojang_namespace": true,
+        },
+        "skinDomains": ["127.0.0.1", "localhost"],
+    })
+}
+
+fn profile(state: &State) -> serde_json::Value {
+    let mut textures = serde_json::Map::new();
+    if let Some(skin) = &state.skin {
+        let mut entry = serde_json::json!({
+            "url": format!("{}/textures/{}", state.api_root, skin.hash),
+        });
+        if state.slim {
+            entry["metadata"] = serde_json::json!({ "model": "slim" });
+        }
+        textures.insert("SKIN".to_owned(), entry);
+    }
+    if let Some(cape) = &state.cape {
+        textures.insert(
+            "CAPE".to_owned(),
+            serde_json::json!({ "url": format!("{}/textures/{}", state.api_root, cape.hash) }),
+        );
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let value = serde_json::json!({
+        "timestamp": timestamp,
+        "profileId": state.profile_id,
+        "profileName": state.name,
+        "textures": textures,
+    });
+
+    serde_json::json!({
+        "id": state.profile_id,
+        "name": state.name,
+        "properties": [{
+            "name": "textures",
+            "value": STANDARD.encode(value.to_string()),
+        }],
+    })
+}
@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use unml_core::{Account, AccountType, AuthProvider, Credentials};
+use uuid::Uuid;
+
+use crate::{Error, Result, http_client};
+
+/// Yggdrasil 角色
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YggdrasilProfile {
+    pub id: String,
+    pub name: String,
+}
+
+/// 多角色账号的角色选择回调，返回选中角色的下标
+pub type ProfileSelector = Box<dyn Fn(&[YggdrasilProfile]) -> Option<usize> + Send + Sync>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticateResponse {
+    access_token: String,
+    client_token: String,
+    #[serde(default)]
+    available_profiles: Vec<YggdrasilProfile>,
+    selected_profile: Option<YggdrasilProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_message: String,
+}
+
+/// Yggdrasil 外置登录（LittleSkin、Ely.by、Blessing Skin 等）
+pub struct YggdrasilAuthProvider {
+    profile_selector: Option<ProfileSelector>,
+}
+
+impl YggdrasilAuthProvider {
+    pub fn new() -> Self {
+        Self {
+            profile_selector: None,
+        }
+    }
+
+    /// 设置角色选择回调（账号有多个角色且服务器未指定时调用）
+    pub fn with_profile_selector(mut self, selector: ProfileSelector) -> Self {
+        self.profile_selector = Some(selector);
+        self
+    }
+
+    /// 解析 API 根地址
+    ///
+    /// 服务器可以通过 `X-Authlib-Injector-API-Location` 头指向真正的 API 地址，
+    /// 该地址可能是相对路径。
+    pub async fn resolve_api_root(server: &str) -> Result<String> {
+        let server = if server.contains("://") {
+            server.to_owned()
+        } else {
+            format!("https://{server}")
+        };
+
+        let response = http_client()
+            .get(&server)
+            .send()
+            .await
+            .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+        let api_root = match response.headers().get("X-Authlib-Injector-API-Location") {
+            Some(location) => {
+                let location = location
+                    .to_str()
+                    .map_err(|e| Error::AuthFailed(format!("Invalid API location: {e}")))?;
+
+                response
+                    .url()
+                    .join(location)
+                    .map_err(|e| Error::AuthFailed(format!("Invalid API location: {e}")))?
+                    .to_string()
+            }
+            None => response.url().to_string(),
+        };
+
+        Ok(api_root.trim_end_matches('/').to_owned())
+    }
+
+    /// 获取 API 元数据，返回 Base64 编码结果（用于
+    /// `-Dauthlibinjector.yggdrasil.prefetched`）
+    pub async fn fetch_metadata(api_root: &str) -> Result<String> {
+        let body = http_client()
+            .get(api_root)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| unml_core::HttpError(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+        // 确保是合法的元数据，而不是错误页面
+        serde_json::from_str::<serde_json::Value>(&body).map_err(unml_core::JsonError)?;
+
+        Ok(STANDARD.encode(body))
+    }
+
+    /// 注销令牌
+    pub async fn invalidate(&self, account: &Account) -> Result<()> {
+        let (api_root, client_token) = Self::server_of(account)?;
+
+        post(
+            api_root,
+            "invalidate",
+            &serde_json::json!({
+                "accessToken": account.access_token,
+                "clientToken": client_token,
+            }),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    fn server_of(account: &Account) -> Result<(&str, &str)> {
+        match &account.account_type {
+            AccountType::Yggdrasil {
+                api_root,
+                client_token,
+                ..
+            } => Ok((api_root, client_token)),
+            _ => Err(Error::AuthFailed(
+                "Yggdrasil provider only supports Yggdrasil accounts".to_owned(),
+            )),
+        }
+    }
+
+    async fn authenticate(&self, server: &str, username: &str, password: &str) -> Result<Account> {
+        let api_root = Self::resolve_api_root(server).await?;
+        let metadata = Self::fetch_metadata(&api_root).await.ok();
+        let client_token = Uuid::new_v4().simple().to_string();
+
+        let response: AuthenticateResponse = post(
+            &api_root,
+            "authenticate",
+            &serde_json::json!({
+                "agent": { "name": "Minecraft", "version": 1 },
+                "username": username,
+                "password": password,
+                "clientToken": client_token,
+                "requestUser": false,
+            }),
+        )
+        .await?
+        .json()
+        .await
+        .map_err(|e| Error::AuthFailed(format!("Failed to parse authenticate response: {e}")))?;
+
+        let mut account = Account {
+            username: String::new(),
+            uuid: String::new(),
+            access_token: response.access_token,
+            refresh_token: None,
+            account_type: AccountType::Yggdrasil {
+                api_root,
+                client_token: response.client_token,
+                metadata,
+            },
+            local_textures: None,
+        };
+
+        if let Some(profile) = response.selected_profile {
+            account.username = profile.name;
+            account.uuid = profile.id;
+            return Ok(account);
+        }
+
+        // 多角色账号需要选择角色，并通过 refresh 绑定到令牌上
+        let profile = self.select_profile(response.available_profiles)?;
+        self.refresh_with_profile(&account, Some(&profile)).await
+    }
+
+    fn select_profile(&self, mut profiles: Vec<YggdrasilProfile>) -> Result<YggdrasilProfile> {
+        let index = match profiles.len() {
+            0 => return Err(Error::ProfileMissing),
+            1 => 0,
+            _ => self
+                .profile_selector
+                .as_ref()
+                .and_then(|selector| selector(&profiles))
+                .filter(|&index| index < profiles.len())
+                .ok_or(Error::ProfileNotSelected)?,
+        };
+
+        Ok(profiles.swap_remove(index))
+    }
+
+    async fn refresh_with_profile(
+        &self,
+        account: &Account,
+        profile: Option<&YggdrasilProfile>,
+    ) -> Result<Account> {
+        let (api_root, client_token) = Self::server_of(account)?;
+
+        let mut request = serde_json::json!({
+            "accessToken": account.access_token,
+            "clientToken": client_token,
+            "requestUser": false,
+        });
+        if let Some(profile) = profile {
+            request["selectedProfile"] = serde_json::json!(profile);
+        }
+
+        let response: AuthenticateResponse = post(api_root, "refresh", &request)
+            .await?
+            .json()
+            .await
+            .map_err(|e| Error::AuthFailed(format!("Failed to parse refresh response: {e}")))?;
+
+        let profile = response.selected_profile.ok_or(Error::ProfileMissing)?;
+
+        let mut refreshed = account.clone();
+        refreshed.username = profile.name;
+        refreshed.uuid = profile.id;
+        refreshed.access_token = response.access_token;
+
+        Ok(refreshed)
+    }
+}
+
+async fn post(
+    api_root: &str,
+    endpoint: &str,
+    body: &serde_json::Value,
+) -> Result<reqwest::Response> {
+    let response = http_client()
+        .post(format!("{api_root}/authserver/{endpoint}"))
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| Error::AuthFailed(format!("Yggdrasil {endpoint} failed: {e}")))?;
+
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(error) if error.error == "ForbiddenOperationException" => {
+            if endpoint == "authenticate" {
+                Err(Error::InvalidCredentials)
+            } else {
+                Err(Error::TokenExpired)
+            }
+        }
+        Ok(error) => Err(Error::AuthFailed(format!(
+            "Yggdrasil {endpoint} failed: {} {}",
+            error.error, error.error_message
+        ))),
+        Err(_) => Err(Error::AuthFailed(format!(
+            "Yggdrasil {endpoint} failed: {body}"
+        ))),
+    }
+}
+
+#[async_trait]
+impl AuthProvider for YggdrasilAuthProvider {
+    type Error = Error;
+
+    async fn login(&self, credentials: Credentials) -> Result<Account> {
+        match credentials {
+            Credentials::Yggdrasil {
+                server,
+                username,
+                password,
+            } => self.authenticate(&server, &username, &password).await,
+            _ => Err(Error::AuthFailed(
+                "Yggdrasil provider only supports Yggdrasil credentials".to_owned(),
+            )),
+        }
+    }
+
+    async fn refresh(&self, account: &Account) -> Result<Account> {
+        let mut refreshed = self.refresh_with_profile(account, None).await?;
+
+        // 顺便更新预取的元数据
+        if let AccountType::Yggdrasil {
+            api_root, metadata, ..
+        } = &mut refreshed.account_type
+            && let Ok(fresh) = Self::fetch_metadata(api_root).await
+        {
+            *metadata = Some(fresh);
+        }
+
+        Ok(refreshed)
+    }
+
+    async fn validate(&self, account: &Account) -> Result<bool> {
+        let (api_root, client_token) = Self::server_of(account)?;
+
+        let result = post(
+            api_root,
+            "validate",
+            &serde_json::json!({
+                "accessToken": account.access_token,
+                "clientToken": client_token,
+            }),
+        )
+        .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(Error::TokenExpired) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Default for YggdrasilAuthProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
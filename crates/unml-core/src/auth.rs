@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -21,8 +23,22 @@ pub trait AuthProvider: Send + Sync {
 /// 登录凭据
 #[derive(Debug, Clone)]
 pub enum Credentials {
-    Offline { username: String },
-    Microsoft { code: String },
+    Offline {
+        username: String,
+        /// 自定义 UUID，留空时由用户名推导
+        uuid: Option<String>,
+    },
+    Microsoft {
+        code: String,
+    },
+    /// Yggdrasil 外置登录（authlib-injector）
+    Yggdrasil {
+        /// 用户输入的认证服务器地址，会通过 `X-Authlib-Injector-API-Location`
+        /// 解析
+        server: String,
+        username: String,
+        password: String,
+    },
 }
 
 /// 账号信息
@@ -35,10 +51,31 @@ pub struct Account {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub refresh_token: Option<String>,
     pub account_type: AccountType,
+    /// 本地皮肤与披风（仅离线账号使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_textures: Option<LocalTextures>,
+}
+
+/// 本地材质文件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalTextures {
+    pub skin: Option<PathBuf>,
+    /// 皮肤是否为纤细（Alex）模型
+    #[serde(default)]
+    pub slim: bool,
+    pub cape: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AccountType {
     Offline,
     Microsoft,
+    Yggdrasil {
+        /// 认证服务器 API 根地址
+        api_root: String,
+        client_token: String,
+        /// Base64 编码的 API 元数据，启动时传给 authlib-injector 以免重复请求
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        metadata: Option<String>,
+    },
 }
@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::Path;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
 
 use crate::UnmlError;
 
@@ -45,6 +47,12 @@ pub enum Checksum {
     Sha256(String),
 }
 
+/// 文件的 SHA-1，文件无法读取时返回 `None`
+pub async fn file_sha1(path: &Path) -> Option<String> {
+    let data = tokio::fs::read(path).await.ok()?;
+    Some(format!("{:x}", Sha1::digest(&data)))
+}
+
 /// 版本清单
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionManifest {
@@ -81,26 +89,180 @@ pub struct VersionInfo {
     pub libraries: Vec<Library>,
     #[serde(rename = "assetIndex")]
     pub asset_index: AssetIndex,
+    /// 1.13 之前的游戏参数，以空格分隔
     #[serde(rename = "minecraftArguments", default)]
-    pub minecraft_arguments: Vec<String>,
+    pub minecraft_arguments: Option<String>,
+    /// 1.13 起的启动参数
+    #[serde(default)]
+    pub arguments: Option<VersionArguments>,
+    #[serde(default)]
+    pub downloads: VersionDownloads,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionArguments {
+    #[serde(default)]
+    pub game: Vec<Argument>,
+    #[serde(default)]
+    pub jvm: Vec<Argument>,
+}
+
+/// 启动参数，可能受平台与特性规则限制
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Argument {
+    Plain(String),
+    Conditional {
+        rules: Vec<Rule>,
+        value: ArgumentValue,
+    },
+}
+
+impl Argument {
+    /// 在启用 `features` 时生效的参数值
+    pub fn values(&self, features: &[&str]) -> &[String] {
+        match self {
+            Self::Plain(value) => std::slice::from_ref(value),
+            Self::Conditional { rules, value } if rules_allow(rules, features) => value.as_slice(),
+            Self::Conditional { .. } => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ArgumentValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl ArgumentValue {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Self::Single(value) => std::slice::from_ref(value),
+            Self::Multiple(values) => values,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    pub action: RuleAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<OsRule>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub features: HashMap<String, bool>,
+}
+
+impl Rule {
+    /// 规则是否适用于当前平台与启用的特性，未启用的特性视为 `false`
+    pub fn applies(&self, features: &[&str]) -> bool {
+        let os_matches = self.os.as_ref().is_none_or(OsRule::matches_current);
+        let features_match = self
+            .features
+            .iter()
+            .all(|(name, expected)| features.contains(&name.as_str()) == *expected);
+
+        os_matches && features_match
+    }
+}
+
+/// 按顺序应用规则，最后一条适用的规则决定结果；没有规则时允许
+pub fn rules_allow(rules: &[Rule], features: &[&str]) -> bool {
+    rules
+        .iter()
+        .rfind(|rule| rule.applies(features))
+        .map_or(rules.is_empty(), |rule| rule.action == RuleAction::Allow)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    Allow,
+    Disallow,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsRule {
+    /// `windows`、`osx` 或 `linux`
+    pub name: Option<String>,
+    pub arch: Option<String>,
+    /// 系统版本的正则表达式，暂不检查
+    pub version: Option<String>,
+}
+
+/// 版本 JSON 中当前系统的名称：`windows`、`osx` 或 `linux`
+fn current_os() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "osx",
+        os => os,
+    }
+}
+
+impl OsRule {
+    pub fn matches_current(&self) -> bool {
+        self.name.as_deref().is_none_or(|name| name == current_os())
+            && self
+                .arch
+                .as_deref()
+                .is_none_or(|arch| arch == std::env::consts::ARCH)
+    }
+}
+
+/// 版本 JSON 中的客户端与服务端 JAR
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionDownloads {
+    pub client: Option<Artifact>,
+    /// 早期版本没有独立服务端
+    pub server: Option<Artifact>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Library {
     pub name: String,
     pub downloads: Option<LibraryDownloads>,
+    /// 平台限制，如只在特定系统上使用的原生库
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<Rule>,
+    /// 1.19 之前的原生库，按系统名称给出分类器，如 `natives-windows-${arch}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub natives: HashMap<String, String>,
+    /// 解压原生库时的排除规则
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extract: Option<ExtractRules>,
 }
 
 impl Library {
     pub fn is_allowed(&self) -> bool {
-        // 简化版本，实际需要检查 rules
-        true
+        rules_allow(&self.rules, &[])
+    }
+
+    /// 当前平台需要解压的原生库分类器，没有原生库时返回 `None`
+    pub fn native_classifier(&self) -> Option<String> {
+        let arch = if cfg!(target_pointer_width = "64") {
+            "64"
+        } else {
+            "32"
+        };
+        self.natives
+            .get(current_os())
+            .map(|classifier| classifier.replace("${arch}", arch))
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractRules {
+    /// 不解压的路径前缀，通常为 `META-INF/`
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LibraryDownloads {
     pub artifact: Option<Artifact>,
+    /// 按分类器区分的原生库
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub classifiers: HashMap<String, Artifact>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,6 +1,11 @@
 use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
 use crate::UnmlError;
 
@@ -47,20 +52,122 @@ pub struct LaunchConfig {
     pub game_args: Vec<String>,
     pub window_width: u32,
     pub window_height: u32,
+    /// 启动后直接进入的世界、服务器或 Realm
+    pub quick_play: Option<QuickPlay>,
 }
 
-/// 游戏进程（占位符）
+/// Quick Play 目标
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuickPlay {
+    /// 存档目录名
+    Singleplayer {
+        world: String,
+    },
+    Multiplayer {
+        host: String,
+        port: Option<u16>,
+    },
+    Realms {
+        realm_id: String,
+    },
+}
+
+/// 游戏或服务端进程
+///
+/// 通过 [`GameProcess::spawn`] 启动时接管标准输入输出：控制台输入逐行写入
+/// 标准输入，标准输出与标准错误合并后逐行读取。
 #[derive(Debug)]
 pub struct GameProcess {
     pid: Option<u32>,
+    child: Option<Child>,
+    console: Option<UnboundedSender<String>>,
+    output: Option<UnboundedReceiver<String>>,
 }
 
 impl GameProcess {
     pub fn new(pid: Option<u32>) -> Self {
-        Self { pid }
+        Self {
+            pid,
+            child: None,
+            console: None,
+            output: None,
+        }
+    }
+
+    /// 启动进程，需要在 Tokio 运行时中调用
+    pub fn spawn(mut command: Command) -> std::io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (output_tx, output_rx) = mpsc::unbounded_channel();
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_lines(stdout, output_tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_lines(stderr, output_tx));
+        }
+
+        let (console_tx, mut console_rx) = mpsc::unbounded_channel::<String>();
+        if let Some(mut stdin) = child.stdin.take() {
+            tokio::spawn(async move {
+                while let Some(line) = console_rx.recv().await {
+                    let line = format!("{}\n", line.trim_end_matches(['\r', '\n']));
+                    if stdin.write_all(line.as_bytes()).await.is_err()
+                        || stdin.flush().await.is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+
+        Ok(Self {
+            pid: child.id(),
+            child: Some(child),
+            console: Some(console_tx),
+            output: Some(output_rx),
+        })
     }
 
     pub fn pid(&self) -> Option<u32> {
         self.pid
     }
+
+    /// 控制台输入，每条消息作为一行写入进程的标准输入
+    pub fn console(&self) -> Option<UnboundedSender<String>> {
+        self.console.clone()
+    }
+
+    /// 取走进程输出，只能取一次
+    pub fn take_output(&mut self) -> Option<UnboundedReceiver<String>> {
+        self.output.take()
+    }
+
+    /// 等待进程退出，未接管进程时返回 `None`
+    pub async fn wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        match &mut self.child {
+            Some(child) => child.wait().await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn kill(&mut self) -> std::io::Result<()> {
+        match &mut self.child {
+            Some(child) => child.kill().await,
+            None => Ok(()),
+        }
+    }
+}
+
+async fn forward_lines(reader: impl AsyncRead + Unpin, tx: UnboundedSender<String>) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if tx.send(line).is_err() {
+            break;
+        }
+    }
 }
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ModLoader;
+
+/// 游戏实例：一个独立的游戏目录及其版本、加载器与启动设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub id: String,
+    pub name: String,
+    pub game_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<LoaderSpec>,
+    #[serde(default)]
+    pub settings: InstanceSettings,
+    /// 从整合包创建时记录整合包信息，用于后续更新
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack: Option<PackInfo>,
+}
+
+impl Instance {
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        game_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            game_version: game_version.into(),
+            loader: None,
+            settings: InstanceSettings::default(),
+            pack: None,
+        }
+    }
+}
+
+/// Mod 加载器及其版本
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoaderSpec {
+    pub loader: ModLoader,
+    pub version: String,
+}
+
+/// 实例的启动设置，未设置的项使用全局设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstanceSettings {
+    pub java_path: Option<PathBuf>,
+    pub min_memory_mb: Option<u32>,
+    pub max_memory_mb: Option<u32>,
+    pub jvm_args: Vec<String>,
+}
+
+/// 整合包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackFormat {
+    Modrinth,
+    CurseForge,
+}
+
+/// 实例来源的整合包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackInfo {
+    pub format: PackFormat,
+    pub name: String,
+    pub version: String,
+}
@@ -2,10 +2,12 @@ mod auth;
 mod download;
 mod error;
 mod game;
+mod instance;
 mod mods;
 
 pub use auth::*;
 pub use download::*;
 pub use error::*;
 pub use game::*;
+pub use instance::*;
 pub use mods::*;
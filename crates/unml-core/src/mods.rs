@@ -40,7 +40,7 @@ pub struct SearchFilters {
     pub category: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ModLoader {
     Forge,
     Fabric,
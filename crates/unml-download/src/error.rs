@@ -18,4 +18,10 @@ pub enum Error {
     Checksum(#[from] unml_core::ChecksumError),
 }
 
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(unml_core::IoError(e))
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
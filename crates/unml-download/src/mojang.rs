@@ -1,6 +1,10 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
+use futures::StreamExt;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
 use unml_core::{Checksum, DownloadProvider, ProgressCallback, VersionInfo, VersionManifest};
 
 use crate::{Error, Result};
@@ -11,6 +15,17 @@ impl MojangDownloadProvider {
     pub fn new() -> Self {
         Self
     }
+
+    async fn get(&self, url: &str) -> Result<reqwest::Response> {
+        let response = crate::http_client()
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -37,20 +52,85 @@ impl DownloadProvider for MojangDownloadProvider {
         Ok(manifest)
     }
 
-    async fn fetch_version_info(&self, _version_id: &str) -> Result<VersionInfo> {
-        // TODO: 实现
-        todo!("fetch_version_info not implemented yet")
+    async fn fetch_version_info(&self, version_id: &str) -> Result<VersionInfo> {
+        let manifest = self.fetch_version_manifest().await?;
+        let version = manifest
+            .versions
+            .iter()
+            .find(|v| v.id == version_id)
+            .ok_or_else(|| Error::VersionNotFound(version_id.to_owned()))?;
+
+        let bytes = self
+            .get(&self.transform_url(&version.url))
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+        Ok(serde_json::from_slice(&bytes).map_err(unml_core::JsonError)?)
     }
 
     async fn download_file(
         &self,
-        _url: &str,
-        _dest: &Path,
-        _checksum: Option<&Checksum>,
-        _progress: Option<ProgressCallback>,
+        url: &str,
+        dest: &Path,
+        checksum: Option<&Checksum>,
+        progress: Option<ProgressCallback>,
     ) -> Result<()> {
-        // TODO: 实现
-        todo!("download_file not implemented yet")
+        let response = self.get(url).await?;
+        let total = response.content_length().unwrap_or(0);
+
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // 追加到完整文件名，避免同名不同扩展名的文件并发下载时冲突
+        let mut tmp = dest.as_os_str().to_owned();
+        tmp.push(".part");
+        let tmp = PathBuf::from(tmp);
+        let mut file = tokio::fs::File::create(&tmp).await?;
+
+        let mut sha1 = Sha1::new();
+        let mut sha256 = Sha256::new();
+        let mut downloaded = 0;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| unml_core::HttpError(e.to_string()))?;
+            match checksum {
+                Some(Checksum::Sha1(_)) => sha1.update(&chunk),
+                Some(Checksum::Sha256(_)) => sha256.update(&chunk),
+                None => {}
+            }
+            file.write_all(&chunk).await?;
+
+            downloaded += chunk.len() as u64;
+            if let Some(progress) = &progress {
+                progress(downloaded, total);
+            }
+        }
+        file.flush().await?;
+        drop(file);
+
+        let expected = match checksum {
+            Some(Checksum::Sha1(expected)) => Some((expected, format!("{:x}", sha1.finalize()))),
+            Some(Checksum::Sha256(expected)) => {
+                Some((expected, format!("{:x}", sha256.finalize())))
+            }
+            None => None,
+        };
+        if let Some((expected, actual)) = expected
+            && !actual.eq_ignore_ascii_case(expected)
+        {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(unml_core::ChecksumError {
+                expected: expected.clone(),
+                actual,
+            }
+            .into());
+        }
+
+        tokio::fs::rename(&tmp, dest).await?;
+
+        Ok(())
     }
 }
 
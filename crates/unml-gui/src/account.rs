@@ -0,0 +1,388 @@
+//! Saved accounts shared by the home sidebar and the skin settings page.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use gpui::*;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use unml_auth::{DefaultSkin, LoginEvent, MicrosoftAuthProvider, MinecraftProfile, SkinCache};
+use unml_core::{Account, AccountType, AuthProvider, Credentials};
+
+use crate::tokio::Tokio;
+
+const ACCOUNTS_FILE: &str = "./unml/accounts.json";
+const SKIN_CACHE_DIR: &str = "./unml/skins";
+/// 头像渲染尺寸，与侧边栏中的显示尺寸一致
+const AVATAR_SIZE: u32 = 96;
+
+/// `accounts.json` 的内容
+#[derive(Default, Serialize, Deserialize)]
+struct AccountsFile {
+    #[serde(default)]
+    accounts: Vec<Account>,
+    /// 当前账号的 UUID
+    #[serde(default)]
+    selected: Option<String>,
+}
+
+/// 正在进行的微软账号登录
+pub struct LoginProgress {
+    /// 最近一次登录进度，`None` 表示正在获取设备码
+    pub event: Option<LoginEvent>,
+    cancel: CancellationToken,
+}
+
+/// 已保存的账号，以及当前账号的 Profile 与头像
+pub struct AccountState {
+    pub accounts: Vec<Account>,
+    selected: Option<String>,
+    /// 当前微软账号的 Profile，包含皮肤与披风列表
+    pub profile: Option<MinecraftProfile>,
+    /// 按 UUID 缓存的头像文件
+    avatars: HashMap<String, PathBuf>,
+    skins: Arc<SkinCache>,
+    /// 正在修改当前账号，期间忽略新的修改
+    pub busy: bool,
+    pub login: Option<LoginProgress>,
+    pub error: Option<String>,
+}
+
+impl Global for AccountState {}
+
+impl AccountState {
+    pub fn init(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            return;
+        }
+
+        cx.set_global(Self {
+            accounts: Vec::new(),
+            selected: None,
+            profile: None,
+            avatars: HashMap::new(),
+            skins: Arc::new(SkinCache::new(SKIN_CACHE_DIR)),
+            busy: false,
+            login: None,
+            error: None,
+        });
+
+        let task = Tokio::spawn(cx, load_accounts());
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                match result {
+                    Ok(Ok(file)) => {
+                        state.accounts = file.accounts;
+                        state.selected = file.selected;
+                    }
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                Self::load_profile(cx);
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    /// 当前账号，未选择时使用第一个账号
+    pub fn current(&self) -> Option<&Account> {
+        self.selected
+            .as_ref()
+            .and_then(|uuid| self.accounts.iter().find(|a| &a.uuid == uuid))
+            .or_else(|| self.accounts.first())
+    }
+
+    pub fn avatar(&self, uuid: &str) -> Option<PathBuf> {
+        self.avatars.get(uuid).cloned()
+    }
+
+    /// 切换到列表中的下一个账号
+    pub fn select_next(cx: &mut App) {
+        let state = cx.global_mut::<Self>();
+        let Some(current) = state.current().map(|a| a.uuid.clone()) else {
+            return;
+        };
+        let index = state
+            .accounts
+            .iter()
+            .position(|a| a.uuid == current)
+            .unwrap_or_default();
+        let next = state.accounts[(index + 1) % state.accounts.len()]
+            .uuid
+            .clone();
+        if next == current {
+            return;
+        }
+
+        state.selected = Some(next);
+        state.profile = None;
+        Self::save(cx);
+        Self::load_profile(cx);
+        cx.refresh_windows();
+    }
+
+    /// 使用设备码登录微软账号，成功后添加或替换同一 UUID 的账号并设为当前账号
+    pub fn login_microsoft(cx: &mut App) {
+        let state = cx.global_mut::<Self>();
+        if state.login.is_some() {
+            return;
+        }
+        let cancel = CancellationToken::new();
+        state.login = Some(LoginProgress {
+            event: None,
+            cancel: cancel.clone(),
+        });
+        state.error = None;
+
+        let (events, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let provider = MicrosoftAuthProvider::new()
+            .with_events(events)
+            .with_cancellation(cancel);
+        let task = Tokio::spawn(cx, async move {
+            provider
+                .login(Credentials::Microsoft {
+                    code: String::new(),
+                })
+                .await
+        });
+
+        // 登录结束后发送端随 provider 一起销毁，接收循环随之退出
+        cx.spawn(async move |cx| {
+            while let Some(event) = receiver.recv().await {
+                let _ = cx.update(|cx| {
+                    if let Some(login) = &mut cx.global_mut::<Self>().login {
+                        login.event = Some(event);
+                    }
+                    cx.refresh_windows();
+                });
+            }
+        })
+        .detach();
+
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                state.login = None;
+                match result {
+                    Ok(Ok(account)) => {
+                        state.selected = Some(account.uuid.clone());
+                        state.profile = None;
+                        match state.accounts.iter_mut().find(|a| a.uuid == account.uuid) {
+                            Some(slot) => *slot = account,
+                            None => state.accounts.push(account),
+                        }
+                        Self::save(cx);
+                        Self::load_profile(cx);
+                    }
+                    Ok(Err(unml_auth::Error::Cancelled)) => {}
+                    Ok(Err(e)) => state.error = Some(login_error_message(&e)),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    pub fn cancel_login(cx: &mut App) {
+        if let Some(login) = &cx.global::<Self>().login {
+            login.cancel.cancel();
+        }
+    }
+
+    /// 在 Tokio 上修改当前账号，完成后保存账号列表并重新获取 Profile 与头像
+    pub fn update<F, Fut>(cx: &mut App, op: F)
+    where
+        F: FnOnce(Account) -> Fut + Send + 'static,
+        Fut: Future<Output = unml_auth::Result<Account>> + Send + 'static,
+    {
+        let state = cx.global_mut::<Self>();
+        if state.busy {
+            return;
+        }
+        let Some(account) = state.current().cloned() else {
+            return;
+        };
+        state.busy = true;
+        state.error = None;
+
+        let task = Tokio::spawn(cx, op(account));
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                state.busy = false;
+                match result {
+                    Ok(Ok(account)) => {
+                        if let Some(slot) =
+                            state.accounts.iter_mut().find(|a| a.uuid == account.uuid)
+                        {
+                            *slot = account;
+                        }
+                        Self::save(cx);
+                        Self::load_profile(cx);
+                    }
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    /// 获取当前账号的 Profile 并渲染头像：微软账号使用 Profile 中的皮肤，
+    /// 离线账号使用本地皮肤，都没有时回退到默认皮肤
+    fn load_profile(cx: &mut App) {
+        let state = cx.global::<Self>();
+        let Some(account) = state.current().cloned() else {
+            return;
+        };
+        let skins = Arc::clone(&state.skins);
+
+        let task = Tokio::spawn(cx, async move {
+            let profile = match account.account_type {
+                AccountType::Microsoft => {
+                    Some(MicrosoftAuthProvider::new().fetch_profile(&account).await)
+                }
+                _ => None,
+            }
+            .transpose();
+
+            let local_skin = account
+                .local_textures
+                .as_ref()
+                .and_then(|t| t.skin.as_ref());
+            let avatar = match local_skin {
+                Some(path) => skins.local_avatar(path, AVATAR_SIZE).await,
+                None => {
+                    let url = profile
+                        .as_ref()
+                        .ok()
+                        .and_then(Option::as_ref)
+                        .and_then(MinecraftProfile::active_skin)
+                        .map_or_else(
+                            || DefaultSkin::for_uuid(&account.uuid).url(),
+                            |skin| skin.url.as_str(),
+                        );
+                    skins.avatar(url, AVATAR_SIZE).await
+                }
+            };
+
+            (account.uuid, profile, avatar)
+        });
+
+        cx.spawn(async move |cx| {
+            let Ok((uuid, profile, avatar)) = task.await else {
+                return;
+            };
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                if state.current().is_none_or(|a| a.uuid != uuid) {
+                    return;
+                }
+                match profile {
+                    Ok(profile) => state.profile = profile,
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                // 头像获取失败时保留首字母占位
+                if let Ok(avatar) = avatar {
+                    state.avatars.insert(uuid, avatar);
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    fn save(cx: &mut App) {
+        let state = cx.global::<Self>();
+        let file = AccountsFile {
+            accounts: state.accounts.clone(),
+            selected: state.current().map(|a| a.uuid.clone()),
+        };
+
+        let task = Tokio::spawn(cx, save_accounts(file));
+        cx.spawn(async move |cx| {
+            if let Ok(Err(e)) = task.await {
+                let _ = cx.update(|cx| {
+                    cx.global_mut::<Self>().error = Some(e.to_string());
+                    cx.refresh_windows();
+                });
+            }
+        })
+        .detach();
+    }
+}
+
+pub fn account_type_label(account_type: &AccountType) -> String {
+    match account_type {
+        AccountType::Offline => t!("account.offline"),
+        AccountType::Microsoft => t!("account.microsoft"),
+        AccountType::Yggdrasil { .. } => t!("account.yggdrasil"),
+    }
+    .to_string()
+}
+
+/// 登录进度的提示文字
+pub fn login_status_label(event: Option<&LoginEvent>) -> String {
+    match event {
+        None => t!("account.login_starting"),
+        Some(LoginEvent::AwaitingUserCode { .. }) => t!("account.login_awaiting_code"),
+        Some(LoginEvent::ExchangingXboxToken) => t!("account.login_xbox"),
+        Some(LoginEvent::AuthorizingXsts) => t!("account.login_xsts"),
+        Some(LoginEvent::AuthenticatingMinecraft) => t!("account.login_minecraft"),
+        Some(LoginEvent::CheckingOwnership) => t!("account.login_ownership"),
+        Some(LoginEvent::FetchingProfile) => t!("account.login_profile"),
+    }
+    .to_string()
+}
+
+/// 把登录错误转换为本地化的处理建议，其他错误使用原始信息
+fn login_error_message(error: &unml_auth::Error) -> String {
+    use unml_auth::Error;
+
+    match error {
+        Error::DeviceCodeExpired => t!("account.error_code_expired"),
+        Error::Declined => t!("account.error_declined"),
+        Error::NoXboxAccount => t!("account.error_no_xbox_account"),
+        Error::XboxRegionBanned => t!("account.error_region_banned"),
+        Error::AdultVerificationRequired => t!("account.error_adult_verification"),
+        Error::ChildAccount => t!("account.error_child_account"),
+        Error::Xsts { code, .. } => t!("account.error_xsts", code = code),
+        Error::NotOwned => t!("account.error_not_owned"),
+        Error::ProfileMissing => t!("account.error_profile_missing"),
+        _ => return error.to_string(),
+    }
+    .to_string()
+}
+
+async fn load_accounts() -> unml_auth::Result<AccountsFile> {
+    match tokio::fs::read(ACCOUNTS_FILE).await {
+        Ok(data) => Ok(serde_json::from_slice(&data).map_err(unml_core::JsonError)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(AccountsFile::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 先写临时文件再替换，避免写入中断时丢失所有账号
+async fn save_accounts(file: AccountsFile) -> unml_auth::Result<()> {
+    let path = PathBuf::from(ACCOUNTS_FILE);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let data = serde_json::to_vec_pretty(&file).map_err(unml_core::JsonError)?;
+    let tmp = path.with_file_name("accounts.json.tmp");
+    tokio::fs::write(&tmp, data).await?;
+    tokio::fs::rename(&tmp, &path).await?;
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
+pub mod avatar;
 pub mod icon_button;
 pub mod instance_card;
 pub mod layout;
 pub mod navbar;
+pub mod quick_play_card;
 pub mod sidebar;
 pub mod titlebar;
@@ -0,0 +1,70 @@
+//! Player head avatar component.
+
+use std::path::PathBuf;
+
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_markup::ui;
+
+/// Renders a pre-rendered head avatar image, falling back to the first letter
+/// of the username while the skin has not been fetched yet.
+#[derive(IntoElement)]
+pub struct Avatar {
+    username: SharedString,
+    image: Option<PathBuf>,
+    size: Pixels,
+}
+
+impl Avatar {
+    pub fn new(username: impl Into<SharedString>) -> Self {
+        Self {
+            username: username.into(),
+            image: None,
+            size: px(96.0),
+        }
+    }
+
+    /// Path of the avatar PNG rendered by `unml_auth::SkinCache::avatar`.
+    pub fn image(mut self, image: Option<PathBuf>) -> Self {
+        self.image = image;
+        self
+    }
+
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl RenderOnce for Avatar {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let size = self.size;
+
+        if let Some(image) = self.image {
+            return ui! {
+                img(image) @[size: size, rounded: px(8.0)] {}
+            }
+            .into_any_element();
+        }
+
+        let first_char = self.username.chars().next().unwrap_or('?').to_string();
+
+        ui! {
+            div @[
+                size: size,
+                rounded: px(8.0),
+                bg: theme.primary,
+                flex,
+                items_center,
+                justify_center,
+                text_color: theme.primary_foreground,
+                text_xl
+            ] {
+                first_char
+            }
+        }
+        .into_any_element()
+    }
+}
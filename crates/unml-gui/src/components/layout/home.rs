@@ -4,6 +4,10 @@ use gpui_component::ActiveTheme;
 use gpui_markup::ui;
 use gpui_router::{IntoLayout, Outlet};
 use rust_i18n::t;
+use unml_auth::LoginEvent;
+
+use crate::account::{AccountState, account_type_label, login_status_label};
+use crate::components::avatar::Avatar;
 
 #[derive(IntoElement, IntoLayout)]
 pub struct HomeLayout {
@@ -53,7 +57,155 @@ impl AccountSidebar {
 
 impl RenderOnce for AccountSidebar {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        AccountState::init(cx);
+
         let theme = cx.theme();
+        let state = cx.global::<AccountState>();
+
+        let (username, account_type, avatar) = match state.current() {
+            Some(account) => (
+                account.username.clone(),
+                account_type_label(&account.account_type),
+                state.avatar(&account.uuid),
+            ),
+            None => (t!("account.none").to_string(), String::new(), None),
+        };
+
+        // 账号选择器：点击切换到下一个账号
+        let selector = (state.accounts.len() > 1).then(|| {
+            let row = ui! {
+                div @[
+                    id: "account-selector",
+                    w_full,
+                    h: px(36.0),
+                    px_3,
+                    rounded: px(6.0),
+                    bg: theme.secondary,
+                    border_1,
+                    border_color: theme.border,
+                    hover: |s| s.bg(theme.secondary_hover),
+                    cursor_pointer,
+                    flex,
+                    items_center,
+                    justify_between,
+                    gap_2,
+                    text_color: theme.foreground
+                ] {
+                    div @[flex, items_center, gap_2, min_w_0] {
+                        Avatar::new(username.clone()).image(avatar.clone()).size(px(20.0)),
+                        div @[truncate] { username.clone() }
+                    },
+                    SharedString::from("▼")
+                }
+            }
+            .on_click(|_, _, cx| AccountState::select_next(cx));
+
+            ui! {
+                div @[w_full, px_2] {
+                    row
+                }
+            }
+        });
+
+        // 微软账号登录进度：显示设备码，点击链接在浏览器中打开验证页面
+        let login = state.login.as_ref().map(|login| {
+            let code = match &login.event {
+                Some(LoginEvent::AwaitingUserCode {
+                    user_code,
+                    verification_uri,
+                    ..
+                }) => {
+                    let uri = verification_uri.clone();
+                    let link = ui! {
+                        div @[
+                            id: "login-link",
+                            text_sm,
+                            text_color: theme.primary,
+                            cursor_pointer,
+                            truncate
+                        ] {
+                            verification_uri.clone()
+                        }
+                    }
+                    .on_click(move |_, _, cx| cx.open_url(&uri));
+
+                    Some(ui! {
+                        div @[flex, flex_col, items_center, gap_1] {
+                            div @[
+                                text_size: px(20.0),
+                                font_weight: FontWeight::BOLD,
+                                text_color: theme.foreground
+                            ] {
+                                user_code.clone()
+                            },
+                            link
+                        }
+                    })
+                }
+                _ => None,
+            };
+
+            let cancel = ui! {
+                div @[
+                    id: "login-cancel",
+                    text_sm,
+                    text_color: theme.muted_foreground,
+                    hover: |s| s.text_color(theme.foreground),
+                    cursor_pointer
+                ] {
+                    t!("account.login_cancel").to_string()
+                }
+            }
+            .on_click(|_, _, cx| AccountState::cancel_login(cx));
+
+            ui! {
+                div @[
+                    w_full,
+                    p_3,
+                    rounded: px(6.0),
+                    border_1,
+                    border_color: theme.border,
+                    flex,
+                    flex_col,
+                    items_center,
+                    gap_2
+                ] {
+                    div @[text_sm, text_color: theme.muted_foreground] {
+                        login_status_label(login.event.as_ref())
+                    },
+                    ..code,
+                    cancel
+                }
+            }
+        });
+
+        let add_account = ui! {
+            div @[
+                id: "account-add",
+                w_full,
+                h: px(36.0),
+                rounded: px(6.0),
+                bg: theme.secondary,
+                hover: |s| s.bg(theme.secondary_active),
+                cursor_pointer,
+                flex,
+                items_center,
+                justify_center,
+                gap_2,
+                text_color: theme.muted_foreground
+            ] {
+                t!("account.add").to_string()
+            }
+        }
+        .on_click(|_, _, cx| AccountState::login_microsoft(cx));
+
+        let error = state.error.clone().map(|error| {
+            ui! {
+                div @[w_full, px_2, text_xs, text_color: theme.danger] {
+                    error
+                }
+            }
+        });
 
         ui! {
             div @[
@@ -69,72 +221,29 @@ impl RenderOnce for AccountSidebar {
                 p_4,
                 gap_4
             ] {
-                // Avatar placeholder (96x96)
-                div @[
-                    w: px(96.0),
-                    h: px(96.0),
-                    rounded: px(8.0),
-                    bg: theme.primary,
-                    flex,
-                    items_center,
-                    justify_center,
-                    text_color: theme.primary_foreground,
-                    text_xl
-                ] {
-                    SharedString::from("S")
-                },
+                // Avatar (96x96)
+                Avatar::new(username.clone()).image(avatar),
                 // Username
                 div @[
                     text_lg,
                     font_weight: FontWeight::MEDIUM,
                     text_color: theme.foreground
                 ] {
-                    SharedString::from("Steve")
+                    username
                 },
                 // Account type label
                 div @[text_sm, text_color: theme.muted_foreground] {
-                    t!("account.microsoft").to_string()
-                },
-                // Account selector
-                div @[w_full, px_2] {
-                    div @[
-                        w_full,
-                        h: px(36.0),
-                        px_3,
-                        rounded: px(6.0),
-                        bg: theme.secondary,
-                        border_1,
-                        border_color: theme.border,
-                        hover: |s| s.bg(theme.secondary_hover),
-                        cursor_pointer,
-                        flex,
-                        items_center,
-                        justify_between,
-                        text_color: theme.foreground
-                    ] {
-                        t!("account.steve_microsoft").to_string(),
-                        SharedString::from("▼")
-                    }
+                    account_type
                 },
+                ..selector,
+                ..error,
                 // Divider
                 div @[w_full, h: px(1.0), bg: theme.border, my_2] {},
+                // Microsoft login progress
+                ..login,
                 // Add account button
                 div @[w_full, px_2] {
-                    div @[
-                        w_full,
-                        h: px(36.0),
-                        rounded: px(6.0),
-                        bg: theme.secondary,
-                        hover: |s| s.bg(theme.secondary_active),
-                        cursor_pointer,
-                        flex,
-                        items_center,
-                        justify_center,
-                        gap_2,
-                        text_color: theme.muted_foreground
-                    ] {
-                        t!("account.add").to_string()
-                    }
+                    add_account
                 }
             }
         }
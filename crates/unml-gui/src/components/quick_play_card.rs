@@ -0,0 +1,63 @@
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_markup::ui;
+use rust_i18n::t;
+use unml_core::QuickPlay;
+
+/// 首页的 Quick Play 快捷方式：直接进入世界、服务器或 Realm
+#[derive(IntoElement)]
+pub struct QuickPlayCard {
+    target: QuickPlay,
+    instance: SharedString,
+}
+
+impl QuickPlayCard {
+    pub fn new(target: QuickPlay, instance: impl Into<SharedString>) -> Self {
+        Self {
+            target,
+            instance: instance.into(),
+        }
+    }
+}
+
+impl RenderOnce for QuickPlayCard {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+
+        let (action, target) = match &self.target {
+            QuickPlay::Singleplayer { world } => (t!("home.play_world"), world.clone()),
+            QuickPlay::Multiplayer { host, port } => (
+                t!("home.join_server"),
+                port.map_or_else(|| host.clone(), |port| format!("{host}:{port}")),
+            ),
+            QuickPlay::Realms { realm_id } => (t!("home.join_realm"), realm_id.clone()),
+        };
+
+        ui! {
+            div @[
+                flex,
+                flex_col,
+                w: px(200.0),
+                p_3,
+                gap_1,
+                bg: theme.secondary,
+                hover: |s| s.bg(theme.secondary_hover),
+                border_1,
+                border_color: theme.border,
+                rounded_md,
+                cursor_pointer
+            ] {
+                div @[text_size: px(12.0), text_color: theme.muted_foreground] {
+                    action.to_string()
+                },
+                div @[text_size: px(16.0), font_weight: FontWeight::BOLD, text_color: theme.foreground] {
+                    target
+                },
+                div @[text_size: px(12.0), text_color: theme.muted_foreground] {
+                    self.instance
+                }
+            }
+        }
+    }
+}
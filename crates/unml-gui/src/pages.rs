@@ -3,9 +3,14 @@ mod home;
 mod mods;
 mod settings;
 mod versions;
+mod worlds;
+
+/// 所有实例所在目录
+pub(crate) const INSTANCES_DIR: &str = "./unml/instances";
 
 pub use downloads::DownloadsPage;
 pub use home::HomePage;
 pub use mods::ModsPage;
 pub use settings::SettingsPage;
 pub use versions::VersionsPage;
+pub use worlds::WorldsPage;
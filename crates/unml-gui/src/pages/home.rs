@@ -1,19 +1,339 @@
+use std::path::Path;
+use std::sync::Arc;
+
 use gpui::prelude::*;
 use gpui::*;
+use gpui_component::ActiveTheme;
 use gpui_component::scroll::ScrollableElement;
 use gpui_markup::ui;
 use rust_i18n::t;
+use unml_auth::LocalTextureServer;
+use unml_core::{Account, GameLauncher, Instance, LaunchConfig, QuickPlay};
+use unml_launcher::{
+    InstanceManager, JavaManager, JvmArgIssue, JvmPreset, MemoryInfo, StandardLauncher, count_mods,
+    merge_jvm_args, parse_address, recommended_heap, validate_jvm_args,
+};
 use unml_macros::PageRoute;
 
+use super::INSTANCES_DIR;
+use super::settings::JavaState;
+use crate::account::AccountState;
 use crate::components::instance_card::InstanceCard;
+use crate::components::quick_play_card::QuickPlayCard;
 use crate::routing::PageView;
+use crate::tokio::Tokio;
+
+/// 版本、库与资源文件所在目录，所有实例共用
+const GAME_ROOT: &str = "./unml/minecraft";
+/// 每个实例最多显示的最近存档数
+const RECENT_WORLDS: usize = 2;
+const DEFAULT_PORT: u16 = 25565;
+
+/// 首页的共享状态
+struct HomeState {
+    manager: Arc<InstanceManager>,
+    /// 各实例最近游玩的存档与服务器列表中的服务器
+    quick_play: Option<Vec<(Instance, QuickPlay)>>,
+    /// 正在启动的实例名称
+    launching: Option<String>,
+    error: Option<String>,
+    /// 上次启动时 JVM 参数中不影响启动的问题
+    warning: Option<String>,
+}
+
+impl Global for HomeState {}
+
+impl HomeState {
+    fn init(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            return;
+        }
+
+        cx.set_global(Self {
+            manager: Arc::new(InstanceManager::new(INSTANCES_DIR)),
+            quick_play: None,
+            launching: None,
+            error: None,
+            warning: None,
+        });
+        Self::refresh(cx);
+    }
+
+    /// 在 Tokio 上读取所有实例的存档与 `servers.dat`
+    fn refresh(cx: &mut App) {
+        let manager = Arc::clone(&cx.global::<Self>().manager);
+        let task = Tokio::spawn(cx, async move {
+            let mut targets = Vec::new();
+            for instance in manager.list().await? {
+                // 存档已按最后游玩时间倒序排列
+                let worlds = manager.worlds(&instance.id).await?;
+                for world in worlds.into_iter().take(RECENT_WORLDS) {
+                    targets.push((
+                        instance.clone(),
+                        QuickPlay::Singleplayer {
+                            world: world.folder,
+                        },
+                    ));
+                }
+
+                let servers = manager.servers(&instance.id).await?;
+                for server in servers.visible() {
+                    let (host, port) = parse_address(&server.ip);
+                    targets.push((
+                        instance.clone(),
+                        QuickPlay::Multiplayer {
+                            host,
+                            port: (port != DEFAULT_PORT).then_some(port),
+                        },
+                    ));
+                }
+            }
+            Ok::<_, unml_launcher::Error>(targets)
+        });
+
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                match result {
+                    Ok(Ok(targets)) => state.quick_play = Some(targets),
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    /// 使用当前账号启动实例并直接进入目标
+    fn launch(cx: &mut App, instance: Instance, target: QuickPlay) {
+        AccountState::init(cx);
+        let Some(account) = cx.global::<AccountState>().current().cloned() else {
+            cx.global_mut::<Self>().error = Some(t!("home.no_account").to_string());
+            cx.refresh_windows();
+            return;
+        };
+
+        let java = JavaState::manager(cx);
+        let state = cx.global_mut::<Self>();
+        if state.launching.is_some() {
+            return;
+        }
+        state.launching = Some(instance.name.clone());
+        state.error = None;
+        state.warning = None;
+        let manager = Arc::clone(&state.manager);
+
+        let task = Tokio::spawn(cx, launch(manager, java, instance, account, target));
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                state.launching = None;
+                match result {
+                    Ok(Ok(issues)) if issues.is_empty() => {}
+                    Ok(Ok(issues)) => {
+                        state.warning = Some(
+                            t!(
+                                "home.jvm_arg_warnings",
+                                issues = jvm_issues_message(&issues)
+                            )
+                            .to_string(),
+                        );
+                    }
+                    Ok(Err(e)) => state.error = Some(launch_error_message(&e)),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+}
+
+/// 把启动错误转换为本地化的信息，其他错误使用原始信息
+fn launch_error_message(error: &unml_launcher::Error) -> String {
+    match error {
+        unml_launcher::Error::InvalidJvmArgs(issues) => {
+            t!("home.jvm_arg_invalid", issues = jvm_issues_message(issues)).to_string()
+        }
+        _ => error.to_string(),
+    }
+}
+
+fn jvm_issues_message(issues: &[JvmArgIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| match issue {
+            JvmArgIssue::Duplicate(arg) => t!("home.jvm_issue_duplicate", arg = arg),
+            JvmArgIssue::ConflictingGc(gcs) => {
+                t!("home.jvm_issue_conflicting_gc", gcs = gcs.join(", "))
+            }
+            JvmArgIssue::Unsupported { arg, reason } => {
+                t!("home.jvm_issue_unsupported", arg = arg, reason = reason)
+            }
+            JvmArgIssue::InvalidHeap(reason) => t!("home.jvm_issue_invalid_heap", reason = reason),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// 选择 Java、计算内存后启动游戏，进程在后台运行直到退出
+///
+/// 返回 JVM 参数中不影响启动的问题，阻止启动的问题作为
+/// [`unml_launcher::Error::InvalidJvmArgs`] 返回。
+async fn launch(
+    manager: Arc<InstanceManager>,
+    java: Arc<JavaManager>,
+    instance: Instance,
+    account: Account,
+    target: QuickPlay,
+) -> unml_launcher::Result<Vec<JvmArgIssue>> {
+    // 列表可能是旧的，以磁盘上的实例设置为准
+    let instance = manager.load(&instance.id).await?;
+    let game_dir = manager.game_dir(&instance.id);
+    let mut launcher = StandardLauncher::new()
+        .with_root(GAME_ROOT)
+        .with_game_dir(&game_dir);
+
+    // 离线账号的本地材质由本地 Yggdrasil 服务提供，以外置登录账号的方式启动
+    let texture_server = match (&account.account_type, &account.local_textures) {
+        (AccountType::Offline, Some(_)) => Some(
+            LocalTextureServer::start(&account)
+                .await
+                .map_err(|e| unml_launcher::Error::LaunchFailed(e.to_string()))?,
+        ),
+        _ => None,
+    };
+    let account = match &texture_server {
+        Some(server) => server.launch_account(&account),
+        None => account,
+    };
+
+    // 外置登录账号需要 authlib-injector，首次使用时下载到游戏目录
+    if matches!(account.account_type, AccountType::Yggdrasil { .. }) {
+        let provider = MojangDownloadProvider::new();
+        let jar = ensure_authlib_injector(&provider, Path::new(GAME_ROOT)).await?;
+        launcher = launcher.with_authlib_injector(jar);
+    }
+
+    let mut requirement = launcher.java_requirement(&instance.game_version).await?;
+    if let Some(loader) = &instance.loader {
+        requirement = requirement.with_loader(loader.loader, &instance.game_version);
+    }
+    // 实例固定的 Java 不可用时报错，不改用其他 Java
+    let java = java.find_for_instance(&instance, &requirement).await?;
+
+    let settings = &instance.settings;
+    let max_memory = settings.max_memory_mb.unwrap_or_else(|| {
+        recommended_heap(
+            MemoryInfo::current(),
+            count_mods(&game_dir.join("mods")),
+            &java,
+        )
+    });
+    // 实例的内存设置与自定义参数覆盖预设中的同名参数
+    let user_args: Vec<String> = settings
+        .min_memory_mb
+        .map(|min_memory| format!("-Xms{min_memory}m"))
+        .into_iter()
+        .chain(settings.jvm_args.iter().cloned())
+        .collect();
+    let jvm_args = merge_jvm_args(JvmPreset::default().args(max_memory, &java), &user_args);
+
+    let issues = validate_jvm_args(&jvm_args, &java);
+    if issues.iter().any(JvmArgIssue::blocks_launch) {
+        return Err(unml_launcher::Error::InvalidJvmArgs(issues));
+    }
+
+    let config = LaunchConfig {
+        java_path: java.executable,
+        jvm_args,
+        game_args: Vec::new(),
+        window_width: 0,
+        window_height: 0,
+        quick_play: Some(target),
+    };
+    let mut process = launcher
+        .launch(&instance.game_version, &account, config)
+        .await?;
+
+    // 不读取输出会使管道写满而阻塞游戏进程
+    tokio::spawn(async move {
+        if let Some(mut output) = process.take_output() {
+            while output.recv().await.is_some() {}
+        }
+        let _ = process.wait().await;
+        // 材质服务需要在游戏运行期间一直存在
+        drop(texture_server);
+    });
+
+    Ok(issues)
+}
 
 #[derive(PageRoute)]
 #[route(id = "", label = "nav.home", icon = LayoutDashboard, plain)]
 pub struct HomePage;
 
 impl PageView for HomePage {
-    fn view(_window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn view(_window: &mut Window, cx: &mut App) -> impl IntoElement {
+        HomeState::init(cx);
+
+        let theme = cx.theme();
+        let state = cx.global::<HomeState>();
+
+        let quick_play: Vec<AnyElement> = match &state.quick_play {
+            None => Vec::new(),
+            Some(targets) if targets.is_empty() => vec![
+                ui! {
+                    div @[text_color: theme.muted_foreground] {
+                        t!("home.quick_play_empty").to_string()
+                    }
+                }
+                .into_any_element(),
+            ],
+            Some(targets) => targets
+                .iter()
+                .enumerate()
+                .map(|(index, (instance, target))| {
+                    let card = QuickPlayCard::new(target.clone(), instance.name.clone());
+                    let (instance, target) = (instance.clone(), target.clone());
+                    let id = SharedString::from(format!("quick-play-{index}"));
+                    ui! {
+                        div @[id: id] {
+                            card
+                        }
+                    }
+                    .on_click(move |_, _, cx| {
+                        HomeState::launch(cx, instance.clone(), target.clone());
+                    })
+                    .into_any_element()
+                })
+                .collect(),
+        };
+
+        let status = match &state.launching {
+            Some(name) => Some(ui! {
+                div @[text_sm, text_color: theme.muted_foreground] {
+                    t!("home.launching", name = name).to_string()
+                }
+            }),
+            None => match (&state.error, &state.warning) {
+                (Some(error), _) => Some(ui! {
+                    div @[text_sm, text_color: theme.danger] {
+                        error.clone()
+                    }
+                }),
+                (None, Some(warning)) => Some(ui! {
+                    div @[text_sm, text_color: theme.warning] {
+                        warning.clone()
+                    }
+                }),
+                (None, None) => None,
+            },
+        };
+
         ui! {
             div @[size_full, overflow_y_scrollbar] {
                 div @[flex, flex_col, gap: px(32.0)] {
@@ -25,6 +345,15 @@ impl PageView for HomePage {
                             InstanceCard::new("Survival World", "1.20.4", "Fabric", rgb(0x4caf50))
                         }
                     },
+                    div @[flex, flex_col, gap: px(16.0)] {
+                        div @[text_size: px(18.0), font_weight: FontWeight::BOLD] {
+                            t!("home.quick_play").to_string()
+                        },
+                        ..status,
+                        div @[flex, flex_row, flex_wrap, gap: px(16.0)] {
+                            ..quick_play
+                        }
+                    },
                     div @[flex, flex_col, gap: px(16.0)] {
                         div @[text_size: px(18.0), font_weight: FontWeight::BOLD] {
                             t!("home.recent").to_string()
@@ -1,9 +1,12 @@
 mod java;
+mod skin;
 
 use gpui::*;
 use gpui_markup::ui;
 use java::JavaSettingsPage;
+pub(crate) use java::JavaState;
 use rust_i18n::t;
+use skin::SkinSettingsPage;
 use unml_macros::PageRoute;
 
 use crate::routing::PageView;
@@ -14,9 +17,10 @@ use crate::routing::PageView;
     variant = Navigation,
     section {
         java => "settings.java",
+        skin => "settings.skin",
     }
 )]
-#[children(JavaSettingsPage)]
+#[children(JavaSettingsPage, SkinSettingsPage)]
 pub struct SettingsPage;
 
 impl PageView for SettingsPage {
@@ -1,10 +1,370 @@
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use gpui::prelude::*;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use gpui_markup::ui;
 use rust_i18n::t;
+use unml_core::Instance;
+use unml_java::{JavaInstallation, JavaManager, JavaSettings, JavaSource};
+use unml_launcher::InstanceManager;
 use unml_macros::SubRoute;
 
+use crate::pages::INSTANCES_DIR;
 use crate::routing::PageView;
+use crate::tokio::Tokio;
+
+const SETTINGS_FILE: &str = "./unml/java.json";
+const CACHE_FILE: &str = "./unml/java-cache.json";
+const RUNTIME_DIR: &str = "./unml/runtimes";
+
+/// Java 设置页的共享状态，启动游戏时也使用其中的 [`JavaManager`]
+pub(crate) struct JavaState {
+    manager: Arc<JavaManager>,
+    installations: Option<Vec<JavaInstallation>>,
+    settings: JavaSettings,
+    /// 可以固定 Java 的实例
+    instances: Vec<Instance>,
+    error: Option<String>,
+}
+
+impl Global for JavaState {}
+
+impl JavaState {
+    fn init(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            return;
+        }
+
+        let manager = JavaManager::new()
+            .with_settings_file(SETTINGS_FILE)
+            .with_cache_file(CACHE_FILE)
+            .with_runtime_dir(RUNTIME_DIR);
+        cx.set_global(Self {
+            manager: Arc::new(manager),
+            installations: None,
+            settings: JavaSettings::default(),
+            instances: Vec::new(),
+            error: None,
+        });
+
+        Self::update(
+            cx,
+            |manager| async move { manager.refresh().await.map(drop) },
+        );
+
+        let task = Tokio::spawn(cx, async move {
+            InstanceManager::new(INSTANCES_DIR).list().await
+        });
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                match result {
+                    Ok(Ok(instances)) => state.instances = instances,
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn manager(cx: &mut App) -> Arc<JavaManager> {
+        Self::init(cx);
+        Arc::clone(&cx.global::<Self>().manager)
+    }
+
+    /// 在 Tokio 上执行操作，完成后重新读取列表与配置并刷新界面
+    fn update<F, Fut>(cx: &mut App, op: F)
+    where
+        F: FnOnce(Arc<JavaManager>) -> Fut + Send + 'static,
+        Fut: Future<Output = unml_java::Result<()>> + Send + 'static,
+    {
+        let manager = Arc::clone(&cx.global::<Self>().manager);
+        let task = Tokio::spawn(cx, async move {
+            op(Arc::clone(&manager)).await?;
+            let installations = manager.get_installations().await?;
+            Ok::<_, unml_java::Error>((installations, manager.settings().await))
+        });
+
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                match result {
+                    Ok(Ok((installations, settings))) => {
+                        state.installations = Some(installations);
+                        state.settings = settings;
+                        state.error = None;
+                    }
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    /// 把固定的 Java 写入实例设置，`None` 表示自动选择
+    fn pin(cx: &mut App, mut instance: Instance, executable: Option<PathBuf>) {
+        instance.settings.java_path = executable;
+        let task = Tokio::spawn(cx, async move {
+            InstanceManager::new(INSTANCES_DIR).save(&instance).await?;
+            Ok::<_, unml_launcher::Error>(instance)
+        });
+
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                match result {
+                    Ok(Ok(instance)) => {
+                        if let Some(slot) = state.instances.iter_mut().find(|i| i.id == instance.id)
+                        {
+                            *slot = instance;
+                        }
+                        state.error = None;
+                    }
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+
+    /// 弹出系统选择框，选中后执行操作
+    fn pick<F, Fut>(cx: &mut App, directories: bool, op: F)
+    where
+        F: FnOnce(Arc<JavaManager>, PathBuf) -> Fut + Send + 'static,
+        Fut: Future<Output = unml_java::Result<()>> + Send + 'static,
+    {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: !directories,
+            directories,
+            multiple: false,
+            prompt: None,
+        });
+
+        cx.spawn(async move |cx| {
+            let Ok(Ok(Some(mut paths))) = paths.await else {
+                return;
+            };
+            let Some(path) = paths.pop() else {
+                return;
+            };
+            let _ = cx.update(|cx| Self::update(cx, move |manager| op(manager, path)));
+        })
+        .detach();
+    }
+}
+
+/// 实例固定的 Java：点击依次切换为自动选择和每个未隐藏的 Java
+#[derive(IntoElement)]
+struct PinRow {
+    instance: Instance,
+    /// 当前固定的 Java
+    label: String,
+    /// 切换到的下一个选项，`None` 表示自动选择
+    next: Option<PathBuf>,
+}
+
+impl RenderOnce for PinRow {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let label = self.label;
+
+        let instance = self.instance.clone();
+        let next = self.next;
+        let selector = ui! {
+            div @[
+                id: SharedString::from(format!("pin-{}", instance.id)),
+                h: px(28.0),
+                px_2,
+                rounded: px(6.0),
+                border_1,
+                border_color: theme.border,
+                hover: |s| s.bg(theme.secondary_hover),
+                cursor_pointer,
+                flex,
+                items_center,
+                gap_2,
+                min_w_0,
+                text_sm
+            ] {
+                div @[truncate] { label },
+                SharedString::from("▼")
+            }
+        }
+        .on_click(move |_, _, cx| JavaState::pin(cx, instance.clone(), next.clone()));
+
+        ui! {
+            div @[
+                flex,
+                items_center,
+                justify_between,
+                gap_4,
+                p_3,
+                rounded_md,
+                border_1,
+                border_color: theme.border,
+                bg: theme.secondary
+            ] {
+                div @[flex, flex_col, gap_1, flex_shrink_0] {
+                    div @[font_weight: FontWeight::SEMIBOLD] { self.instance.name },
+                    div @[text_xs, text_color: theme.muted_foreground] {
+                        self.instance.game_version
+                    }
+                },
+                selector
+            }
+        }
+    }
+}
+
+fn source_label(source: JavaSource) -> String {
+    match source {
+        JavaSource::Environment => t!("settings.java_source_environment"),
+        JavaSource::Path => t!("settings.java_source_path"),
+        JavaSource::System => t!("settings.java_source_system"),
+        JavaSource::SearchPath => t!("settings.java_source_search_path"),
+        JavaSource::Custom => t!("settings.java_source_custom"),
+        JavaSource::Managed => t!("settings.java_source_managed"),
+    }
+    .to_string()
+}
+
+#[derive(IntoElement)]
+struct JavaRow {
+    installation: JavaInstallation,
+    is_default: bool,
+    hidden: bool,
+}
+
+impl RenderOnce for JavaRow {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let installation = self.installation;
+        let id = installation.executable.to_string_lossy().to_string();
+
+        let details = [
+            Some(format!("Java {}", installation.version)),
+            installation.vendor.clone(),
+            installation.arch.clone(),
+            Some(source_label(installation.source)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        let action = |suffix: &str, label: String| {
+            ui! {
+                div @[
+                    id: SharedString::from(format!("{id}-{suffix}")),
+                    h: px(28.0),
+                    px_2,
+                    rounded: px(6.0),
+                    border_1,
+                    border_color: theme.border,
+                    hover: |s| s.bg(theme.secondary_hover),
+                    cursor_pointer,
+                    flex,
+                    items_center,
+                    text_sm
+                ] {
+                    label
+                }
+            }
+        };
+
+        let executable = installation.executable.clone();
+        let default_action = if self.is_default {
+            action("default", t!("settings.java_unset_default").to_string()).on_click(|_, _, cx| {
+                JavaState::update(cx, |m| async move { m.set_default(None).await })
+            })
+        } else {
+            let executable = executable.clone();
+            action("default", t!("settings.java_set_default").to_string()).on_click(
+                move |_, _, cx| {
+                    let executable = executable.clone();
+                    JavaState::update(cx, |m| async move { m.set_default(Some(executable)).await })
+                },
+            )
+        };
+
+        let hidden = self.hidden;
+        let hide_label = if hidden {
+            t!("settings.java_show")
+        } else {
+            t!("settings.java_hide")
+        };
+        let hide_action = {
+            let executable = executable.clone();
+            action("hide", hide_label.to_string()).on_click(move |_, _, cx| {
+                let executable = executable.clone();
+                JavaState::update(cx, move |m| async move {
+                    m.set_hidden(&executable, !hidden).await
+                })
+            })
+        };
+
+        let remove_action = (installation.source == JavaSource::Custom).then(|| {
+            action("remove", t!("settings.java_remove").to_string()).on_click(move |_, _, cx| {
+                let executable = executable.clone();
+                JavaState::update(cx, |m| async move { m.remove_custom(&executable).await })
+            })
+        });
+
+        let mut badges = Vec::new();
+        if self.is_default {
+            badges.push(t!("settings.java_default").to_string());
+        }
+        if hidden {
+            badges.push(t!("settings.java_hidden").to_string());
+        }
+
+        ui! {
+            div @[
+                flex,
+                items_center,
+                justify_between,
+                gap_4,
+                p_3,
+                rounded_md,
+                border_1,
+                border_color: theme.border,
+                bg: theme.secondary,
+                opacity: if hidden { 0.6 } else { 1.0 }
+            ] {
+                div @[flex, flex_col, gap_1, min_w_0] {
+                    div @[flex, items_center, gap_2] {
+                        div @[font_weight: FontWeight::SEMIBOLD] { details },
+                        ..badges.into_iter().map(|badge| ui! {
+                            div @[text_xs, px_1, rounded_sm, bg: theme.primary, text_color: theme.primary_foreground] {
+                                badge
+                            }
+                        })
+                    },
+                    div @[text_xs, text_color: theme.muted_foreground, truncate] {
+                        installation.executable.to_string_lossy().to_string()
+                    }
+                },
+                div @[flex, gap_2, flex_shrink_0] {
+                    default_action,
+                    hide_action,
+                    ..remove_action
+                }
+            }
+        }
+    }
+}
 
 #[derive(SubRoute)]
 #[subroute(id = "java")]
@@ -13,16 +373,142 @@ pub struct JavaSettingsPage;
 
 impl PageView for JavaSettingsPage {
     fn view(_window: &mut Window, cx: &mut App) -> impl IntoElement {
+        JavaState::init(cx);
+
         let theme = cx.theme();
+        let state = cx.global::<JavaState>();
+
+        let action = |id: &'static str, label: String| {
+            ui! {
+                div @[
+                    id: id,
+                    h: px(36.0),
+                    px_3,
+                    rounded: px(6.0),
+                    bg: theme.secondary,
+                    border_1,
+                    border_color: theme.border,
+                    hover: |s| s.bg(theme.secondary_hover),
+                    cursor_pointer,
+                    flex,
+                    items_center,
+                    text_color: theme.foreground
+                ] {
+                    label
+                }
+            }
+        };
+
+        let content: AnyElement = match &state.installations {
+            None => ui! {
+                div @[flex, items_center, justify_center, h: px(200.), text_color: theme.muted_foreground] {
+                    t!("settings.java_loading").to_string()
+                }
+            }
+            .into_any_element(),
+            Some(installations) if installations.is_empty() => ui! {
+                div @[flex, items_center, justify_center, h: px(200.), text_color: theme.muted_foreground] {
+                    t!("settings.java_not_found").to_string()
+                }
+            }
+            .into_any_element(),
+            Some(installations) => {
+                let rows = installations.iter().map(|installation| JavaRow {
+                    installation: installation.clone(),
+                    is_default: state.settings.default.as_ref() == Some(&installation.executable),
+                    hidden: state.settings.is_hidden(&installation.executable),
+                });
+
+                ui! {
+                    div @[flex, flex_col, gap_2] {
+                        ..rows
+                    }
+                }
+                .into_any_element()
+            }
+        };
+
+        let error = state.error.clone().map(|error| {
+            ui! {
+                div @[text_sm, text_color: theme.danger] {
+                    error
+                }
+            }
+        });
+
+        // 每个实例的可选项为自动选择加上所有未隐藏的 Java
+        let options: Vec<&JavaInstallation> = state
+            .installations
+            .iter()
+            .flatten()
+            .filter(|i| !state.settings.is_hidden(&i.executable))
+            .collect();
+        let pins: Vec<PinRow> = state
+            .instances
+            .iter()
+            .map(|instance| {
+                let executable = instance.settings.java_path.as_ref();
+                let position =
+                    executable.and_then(|path| options.iter().position(|i| &i.executable == path));
+                let next = match position {
+                    Some(index) => options.get(index + 1),
+                    None if executable.is_some() => None,
+                    None => options.first(),
+                };
+                // 固定的 Java 已被隐藏或删除时只显示路径
+                let label = match (executable, position) {
+                    (Some(_), Some(index)) => format!(
+                        "Java {} · {}",
+                        options[index].version,
+                        options[index].executable.display()
+                    ),
+                    (Some(path), None) => path.display().to_string(),
+                    (None, _) => t!("settings.java_pin_auto").to_string(),
+                };
+                PinRow {
+                    instance: instance.clone(),
+                    label,
+                    next: next.map(|i| i.executable.clone()),
+                }
+            })
+            .collect();
+        let pinning = (!pins.is_empty()).then(|| {
+            ui! {
+                div @[flex, flex_col, gap_2] {
+                    div @[text_size: px(16.0), font_weight: FontWeight::BOLD] {
+                        t!("settings.java_pin_title").to_string()
+                    },
+                    div @[text_sm, text_color: theme.muted_foreground] {
+                        t!("settings.java_pin_desc").to_string()
+                    },
+                    ..pins
+                }
+            }
+        });
 
         ui! {
             div @[flex, flex_col, gap: px(16.)] {
                 div @[text_color: theme.muted_foreground] {
                     t!("settings.java_desc").to_string()
                 },
-                div @[flex, items_center, justify_center, h: px(200.), text_color: theme.muted_foreground] {
-                    "Java settings placeholder - to be implemented"
-                }
+                div @[flex, gap_2] {
+                    action("java-refresh", t!("settings.java_refresh").to_string()).on_click(|_, _, cx| {
+                        JavaState::update(cx, |m| async move { m.refresh().await.map(drop) })
+                    }),
+                    action("java-add", t!("settings.java_add").to_string()).on_click(|_, _, cx| {
+                        JavaState::pick(cx, false, |m, path| async move {
+                            m.add_custom(path).await.map(drop)
+                        })
+                    }),
+                    action("java-scan", t!("settings.java_scan_folder").to_string()).on_click(|_, _, cx| {
+                        JavaState::pick(cx, true, |m, path| async move {
+                            m.scan_folder(path).await.map(drop)
+                        })
+                    })
+                },
+                ..error,
+                content,
+                ..pinning
             }
         }
     }
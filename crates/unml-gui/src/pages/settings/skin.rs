@@ -0,0 +1,283 @@
+use std::future::Future;
+use std::path::PathBuf;
+
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_markup::ui;
+use rust_i18n::t;
+use unml_auth::{MicrosoftAuthProvider, OfflineAuthProvider, SkinVariant, TextureState};
+use unml_core::{Account, AccountType};
+use unml_macros::SubRoute;
+
+use crate::account::AccountState;
+use crate::components::avatar::Avatar;
+use crate::routing::PageView;
+
+/// 弹出文件选择框，选中后在 Tokio 上修改当前账号
+fn pick<F, Fut>(cx: &mut App, op: F)
+where
+    F: FnOnce(Account, PathBuf) -> Fut + Send + 'static,
+    Fut: Future<Output = unml_auth::Result<Account>> + Send + 'static,
+{
+    let paths = cx.prompt_for_paths(PathPromptOptions {
+        files: true,
+        directories: false,
+        multiple: false,
+        prompt: None,
+    });
+
+    cx.spawn(async move |cx| {
+        let Ok(Ok(Some(mut paths))) = paths.await else {
+            return;
+        };
+        let Some(path) = paths.pop() else {
+            return;
+        };
+        let _ = cx.update(|cx| AccountState::update(cx, move |account| op(account, path)));
+    })
+    .detach();
+}
+
+/// 微软账号上传到 Mojang，离线账号记录为本地皮肤
+fn upload_skin(cx: &mut App, variant: SkinVariant) {
+    pick(cx, move |mut account, path| async move {
+        if let AccountType::Microsoft = account.account_type {
+            let png = tokio::fs::read(&path).await?;
+            MicrosoftAuthProvider::new()
+                .upload_skin(&account, png, variant)
+                .await?;
+        } else {
+            let cape = account.local_textures.as_ref().and_then(|t| t.cape.clone());
+            OfflineAuthProvider::set_local_textures(
+                &mut account,
+                Some(&path),
+                variant == SkinVariant::Slim,
+                cape.as_deref(),
+            )
+            .await?;
+        }
+        Ok(account)
+    });
+}
+
+fn reset_skin(cx: &mut App) {
+    AccountState::update(cx, |mut account| async move {
+        if let AccountType::Microsoft = account.account_type {
+            MicrosoftAuthProvider::new().reset_skin(&account).await?;
+        } else {
+            let cape = account.local_textures.as_ref().and_then(|t| t.cape.clone());
+            OfflineAuthProvider::set_local_textures(&mut account, None, false, cape.as_deref())
+                .await?;
+        }
+        Ok(account)
+    });
+}
+
+/// 离线账号的本地披风，`None` 表示不显示披风
+async fn set_local_cape(mut account: Account, cape: Option<PathBuf>) -> unml_auth::Result<Account> {
+    let textures = account.local_textures.clone().unwrap_or_default();
+    OfflineAuthProvider::set_local_textures(
+        &mut account,
+        textures.skin.as_deref(),
+        textures.slim,
+        cape.as_deref(),
+    )
+    .await?;
+    Ok(account)
+}
+
+fn hide_cape(cx: &mut App) {
+    AccountState::update(cx, |account| async move {
+        if let AccountType::Microsoft = account.account_type {
+            MicrosoftAuthProvider::new().hide_cape(&account).await?;
+            Ok(account)
+        } else {
+            set_local_cape(account, None).await
+        }
+    });
+}
+
+#[derive(SubRoute)]
+#[subroute(id = "skin")]
+#[layout(title = "settings.skin_title")]
+pub struct SkinSettingsPage;
+
+impl PageView for SkinSettingsPage {
+    fn view(_window: &mut Window, cx: &mut App) -> impl IntoElement {
+        AccountState::init(cx);
+
+        let theme = cx.theme();
+        let state = cx.global::<AccountState>();
+
+        let action = |id: SharedString, label: String, active: bool| {
+            ui! {
+                div @[
+                    id: id,
+                    h: px(36.0),
+                    px_3,
+                    rounded: px(6.0),
+                    bg: if active { theme.primary } else { theme.secondary },
+                    border_1,
+                    border_color: theme.border,
+                    hover: |s| s.bg(theme.secondary_hover),
+                    cursor_pointer,
+                    flex,
+                    items_center,
+                    text_color: if active { theme.primary_foreground } else { theme.foreground }
+                ] {
+                    label
+                }
+            }
+        };
+
+        let Some(account) = state.current() else {
+            return ui! {
+                div @[text_color: theme.muted_foreground] {
+                    t!("settings.skin_no_account").to_string()
+                }
+            }
+            .into_any_element();
+        };
+
+        let avatar = Avatar::new(account.username.clone()).image(state.avatar(&account.uuid));
+
+        // 外置登录的皮肤由认证服务器管理
+        if let AccountType::Yggdrasil { .. } = account.account_type {
+            return ui! {
+                div @[flex, items_center, gap: px(16.)] {
+                    avatar,
+                    div @[text_color: theme.muted_foreground] {
+                        t!("settings.skin_yggdrasil").to_string()
+                    }
+                }
+            }
+            .into_any_element();
+        }
+
+        let microsoft = matches!(account.account_type, AccountType::Microsoft);
+
+        let capes: Vec<AnyElement> = if microsoft {
+            let profile = state.profile.as_ref();
+            let capes = profile.map(|p| p.capes.clone()).unwrap_or_default();
+            let equipped = profile.and_then(|p| p.active_cape()).is_some();
+
+            let mut buttons = vec![
+                action(
+                    "cape-hide".into(),
+                    t!("settings.cape_none").to_string(),
+                    !equipped,
+                )
+                .on_click(|_, _, cx| hide_cape(cx))
+                .into_any_element(),
+            ];
+            buttons.extend(capes.into_iter().map(|cape| {
+                let active = cape.state == TextureState::Active;
+                let id = cape.id.clone();
+                action(format!("cape-{}", cape.id).into(), cape.alias, active)
+                    .on_click(move |_, _, cx| {
+                        let id = id.clone();
+                        AccountState::update(cx, move |account| async move {
+                            MicrosoftAuthProvider::new()
+                                .equip_cape(&account, &id)
+                                .await?;
+                            Ok(account)
+                        });
+                    })
+                    .into_any_element()
+            }));
+            buttons
+        } else {
+            let cape = account.local_textures.as_ref().and_then(|t| t.cape.clone());
+            let cape_label = cape.as_ref().and_then(|path| path.file_name()).map_or_else(
+                || t!("settings.cape_choose_file").to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+
+            vec![
+                action(
+                    "cape-hide".into(),
+                    t!("settings.cape_none").to_string(),
+                    cape.is_none(),
+                )
+                .on_click(|_, _, cx| hide_cape(cx))
+                .into_any_element(),
+                action("cape-file".into(), cape_label, cape.is_some())
+                    .on_click(|_, _, cx| {
+                        pick(cx, |account, path| set_local_cape(account, Some(path)));
+                    })
+                    .into_any_element(),
+            ]
+        };
+
+        let status = if state.busy {
+            Some(ui! {
+                div @[text_sm, text_color: theme.muted_foreground] {
+                    t!("settings.skin_updating").to_string()
+                }
+            })
+        } else {
+            state.error.clone().map(|error| {
+                ui! {
+                    div @[text_sm, text_color: theme.danger] {
+                        error
+                    }
+                }
+            })
+        };
+
+        let description = if microsoft {
+            t!("settings.skin_desc")
+        } else {
+            t!("settings.skin_desc_offline")
+        };
+
+        let upload_classic = action(
+            "skin-upload-classic".into(),
+            t!("settings.skin_upload_classic").to_string(),
+            false,
+        )
+        .on_click(|_, _, cx| upload_skin(cx, SkinVariant::Classic));
+        let upload_slim = action(
+            "skin-upload-slim".into(),
+            t!("settings.skin_upload_slim").to_string(),
+            false,
+        )
+        .on_click(|_, _, cx| upload_skin(cx, SkinVariant::Slim));
+        let reset = action(
+            "skin-reset".into(),
+            t!("settings.skin_reset").to_string(),
+            false,
+        )
+        .on_click(|_, _, cx| reset_skin(cx));
+
+        ui! {
+            div @[flex, flex_col, gap: px(16.)] {
+                div @[text_color: theme.muted_foreground] {
+                    description.to_string()
+                },
+                div @[flex, items_center, gap: px(16.)] {
+                    avatar,
+                    div @[flex, flex_col, gap_2] {
+                        div @[flex, gap_2] {
+                            upload_classic,
+                            upload_slim
+                        },
+                        reset
+                    }
+                },
+                ..status,
+                div @[text_size: px(16.0), font_weight: FontWeight::BOLD] {
+                    t!("settings.capes").to_string()
+                },
+                div @[flex, flex_wrap, gap_2] {
+                    ..capes
+                },
+                div @[text_sm, text_color: theme.muted_foreground] {
+                    t!("settings.skin_requirements").to_string()
+                }
+            }
+        }
+        .into_any_element()
+    }
+}
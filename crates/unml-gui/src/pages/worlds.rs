@@ -0,0 +1,290 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_component::scroll::ScrollableElement;
+use gpui_markup::ui;
+use rust_i18n::t;
+use unml_core::Instance;
+use unml_launcher::{Difficulty, GameMode, InstanceManager, WorldInfo};
+use unml_macros::PageRoute;
+
+use super::INSTANCES_DIR;
+use crate::routing::PageView;
+use crate::tokio::Tokio;
+
+/// 存档页的共享状态
+struct WorldsState {
+    manager: Arc<InstanceManager>,
+    worlds: Option<Vec<(Instance, Vec<WorldInfo>)>>,
+    error: Option<String>,
+}
+
+impl Global for WorldsState {}
+
+impl WorldsState {
+    fn init(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            return;
+        }
+
+        cx.set_global(Self {
+            manager: Arc::new(InstanceManager::new(INSTANCES_DIR)),
+            worlds: None,
+            error: None,
+        });
+        Self::refresh(cx);
+    }
+
+    /// 在 Tokio 上重新扫描所有实例的存档并刷新界面
+    fn refresh(cx: &mut App) {
+        let manager = Arc::clone(&cx.global::<Self>().manager);
+        let task = Tokio::spawn(cx, async move {
+            let mut worlds = Vec::new();
+            for instance in manager.list().await? {
+                let saves = manager.worlds(&instance.id).await?;
+                if !saves.is_empty() {
+                    worlds.push((instance, saves));
+                }
+            }
+            Ok::<_, unml_launcher::Error>(worlds)
+        });
+
+        cx.spawn(async move |cx| {
+            let result = task.await;
+            let _ = cx.update(|cx| {
+                let state = cx.global_mut::<Self>();
+                match result {
+                    Ok(Ok(worlds)) => {
+                        state.worlds = Some(worlds);
+                        state.error = None;
+                    }
+                    Ok(Err(e)) => state.error = Some(e.to_string()),
+                    Err(e) => state.error = Some(e.to_string()),
+                }
+                cx.refresh_windows();
+            });
+        })
+        .detach();
+    }
+}
+
+fn game_mode_label(world: &WorldInfo) -> Option<String> {
+    if world.hardcore {
+        return Some(t!("worlds.hardcore").to_string());
+    }
+    let label = match world.game_mode? {
+        GameMode::Survival => t!("worlds.survival"),
+        GameMode::Creative => t!("worlds.creative"),
+        GameMode::Adventure => t!("worlds.adventure"),
+        GameMode::Spectator => t!("worlds.spectator"),
+    };
+    Some(label.to_string())
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Peaceful => t!("worlds.peaceful"),
+        Difficulty::Easy => t!("worlds.easy"),
+        Difficulty::Normal => t!("worlds.normal"),
+        Difficulty::Hard => t!("worlds.hard"),
+    }
+    .to_string()
+}
+
+fn last_played_label(last_played: SystemTime) -> String {
+    let minutes = SystemTime::now()
+        .duration_since(last_played)
+        .unwrap_or_default()
+        .as_secs()
+        / 60;
+    match minutes {
+        0 => t!("worlds.just_now"),
+        1..60 => t!("worlds.minutes_ago", count = minutes),
+        60..1440 => t!("worlds.hours_ago", count = minutes / 60),
+        _ => t!("worlds.days_ago", count = minutes / 1440),
+    }
+    .to_string()
+}
+
+fn size_label(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[derive(IntoElement)]
+struct WorldRow {
+    world: WorldInfo,
+}
+
+impl RenderOnce for WorldRow {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let world = self.world;
+
+        let icon: AnyElement = match world.icon.clone() {
+            Some(icon) => ui! {
+                img(icon) @[size: px(48.0), rounded: px(6.0), flex_shrink_0] {}
+            }
+            .into_any_element(),
+            None => ui! {
+                div @[
+                    size: px(48.0),
+                    rounded: px(6.0),
+                    flex_shrink_0,
+                    bg: theme.background,
+                    flex,
+                    items_center,
+                    justify_center,
+                    text_color: theme.muted_foreground
+                ] {
+                    world.name.chars().next().unwrap_or('?').to_string()
+                }
+            }
+            .into_any_element(),
+        };
+
+        let details = [
+            game_mode_label(&world),
+            world.difficulty.map(difficulty_label),
+            world.version_name.clone(),
+            world.last_played.map(last_played_label),
+            Some(size_label(world.size)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        let seed = world
+            .seed
+            .map(|seed| format!("{} {seed}", t!("worlds.seed")));
+
+        ui! {
+            div @[
+                flex,
+                items_center,
+                gap_3,
+                p_3,
+                rounded_md,
+                border_1,
+                border_color: theme.border,
+                bg: theme.secondary
+            ] {
+                icon,
+                div @[flex, flex_col, gap_1, min_w_0] {
+                    div @[flex, items_center, gap_2] {
+                        div @[font_weight: FontWeight::SEMIBOLD] { world.name },
+                        div @[text_xs, text_color: theme.muted_foreground, truncate] {
+                            world.folder
+                        }
+                    },
+                    div @[text_sm, text_color: theme.muted_foreground] { details },
+                    ..seed.map(|seed| ui! {
+                        div @[text_xs, text_color: theme.muted_foreground] { seed }
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[derive(PageRoute)]
+#[route(id = "worlds", label = "nav.worlds", icon = Globe, plain)]
+pub struct WorldsPage;
+
+impl PageView for WorldsPage {
+    fn view(_window: &mut Window, cx: &mut App) -> impl IntoElement {
+        WorldsState::init(cx);
+
+        let theme = cx.theme();
+        let state = cx.global::<WorldsState>();
+
+        let content: AnyElement = match &state.worlds {
+            None => ui! {
+                div @[flex, items_center, justify_center, h: px(200.), text_color: theme.muted_foreground] {
+                    t!("worlds.loading").to_string()
+                }
+            }
+            .into_any_element(),
+            Some(worlds) if worlds.is_empty() => ui! {
+                div @[flex, items_center, justify_center, h: px(200.), text_color: theme.muted_foreground] {
+                    t!("worlds.empty").to_string()
+                }
+            }
+            .into_any_element(),
+            Some(worlds) => {
+                let groups = worlds.iter().map(|(instance, worlds)| {
+                    let rows = worlds.iter().map(|world| WorldRow { world: world.clone() });
+                    ui! {
+                        div @[flex, flex_col, gap_2] {
+                            div @[text_size: px(18.0), font_weight: FontWeight::BOLD] {
+                                instance.name.clone()
+                            },
+                            ..rows
+                        }
+                    }
+                });
+
+                ui! {
+                    div @[flex, flex_col, gap: px(24.0)] {
+                        ..groups
+                    }
+                }
+                .into_any_element()
+            }
+        };
+
+        let error = state.error.clone().map(|error| {
+            ui! {
+                div @[text_sm, text_color: theme.danger] {
+                    error
+                }
+            }
+        });
+
+        let refresh = ui! {
+            div @[
+                id: "worlds-refresh",
+                h: px(36.0),
+                px_3,
+                rounded: px(6.0),
+                bg: theme.secondary,
+                border_1,
+                border_color: theme.border,
+                hover: |s| s.bg(theme.secondary_hover),
+                cursor_pointer,
+                flex,
+                items_center,
+                text_color: theme.foreground
+            ] {
+                t!("worlds.refresh").to_string()
+            }
+        }
+        .on_click(|_, _, cx| WorldsState::refresh(cx));
+
+        ui! {
+            div @[size_full, overflow_y_scrollbar] {
+                div @[flex, flex_col, gap: px(16.)] {
+                    div @[flex, gap_2] {
+                        refresh
+                    },
+                    ..error,
+                    content
+                }
+            }
+        }
+    }
+}
@@ -4,11 +4,12 @@
 //! routing system. Each page implements the `PageRoute` trait via the
 //! `#[derive(PageRoute)]` macro.
 
-use crate::pages::{DownloadsPage, HomePage, ModsPage, SettingsPage, VersionsPage};
+use crate::pages::{DownloadsPage, HomePage, ModsPage, SettingsPage, VersionsPage, WorldsPage};
 
 crate::define_routes![
     HomePage,
     VersionsPage,
+    WorldsPage,
     ModsPage,
     DownloadsPage,
     SettingsPage,
@@ -1,6 +1,7 @@
+pub(crate) mod cache;
 mod platform;
-mod probe;
-mod scanner;
+pub(crate) mod probe;
+pub(crate) mod scanner;
 
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -13,13 +14,27 @@ use self::platform::MacOSDetector;
 #[cfg(windows)]
 use self::platform::WindowsDetector;
 use self::probe::JavaProbe;
-use crate::{Error, JavaInstallation, JavaVersion, Result};
+use self::scanner::DirectoryScanner;
+use crate::{Error, JavaInstallation, JavaRequirement, JavaSource, RejectedJava, Result};
 
-pub struct JavaDetector;
+/// 用户额外指定的目录的最大扫描深度
+const SEARCH_PATH_DEPTH: u32 = 3;
+
+pub struct JavaDetector {
+    search_paths: Vec<PathBuf>,
+}
 
 impl JavaDetector {
     pub fn new() -> Self {
-        Self
+        Self {
+            search_paths: Vec::new(),
+        }
+    }
+
+    /// 额外扫描的目录，会递归查找其中的 Java
+    pub fn with_search_paths(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.search_paths.extend(paths);
+        self
     }
 
     pub async fn detect(&self) -> Result<Vec<JavaInstallation>> {
@@ -50,21 +65,54 @@ impl JavaDetector {
             }
         }
 
+        // 4. User-configured search paths
+        for path in &self.search_paths {
+            let Ok(found) = DirectoryScanner::scan_recursive(path, SEARCH_PATH_DEPTH).await else {
+                continue;
+            };
+            for mut installation in found {
+                installation.source = JavaSource::SearchPath;
+                let canonical = self.get_canonical_path(&installation.executable);
+                if seen_paths.insert(canonical) {
+                    installations.push(installation);
+                }
+            }
+        }
+
         installations.retain(|inst| self.is_valid_installation(inst));
 
         // descending
-        installations.sort_by(|a, b| b.major_version.cmp(&a.major_version));
+        installations.sort_by_key(|inst| std::cmp::Reverse(inst.major_version));
 
         Ok(installations)
     }
 
-    pub async fn find_suitable(&self, required: JavaVersion) -> Result<JavaInstallation> {
-        let installations = self.detect().await?;
+    /// 满足要求的 Java：优先使用首选主版本，其余按主版本从新到旧
+    pub async fn find_suitable(
+        &self,
+        requirement: impl Into<JavaRequirement>,
+    ) -> Result<JavaInstallation> {
+        let requirement = requirement.into();
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+
+        for installation in self.detect().await? {
+            match requirement.check(&installation) {
+                Ok(()) => accepted.push(installation),
+                Err(reason) => rejected.push(RejectedJava {
+                    installation,
+                    reason,
+                }),
+            }
+        }
+
+        // detect 已按主版本降序排列，稳定排序会保留这一顺序
+        accepted.sort_by_key(|i| !requirement.is_preferred(i.major_version));
 
-        installations
-            .into_iter()
-            .find(|i| i.satisfies(required))
-            .ok_or(Error::NoSuitableJava(required.major))
+        accepted.into_iter().next().ok_or(Error::NoSuitableJava {
+            requirement,
+            rejected,
+        })
     }
 
     async fn detect_from_env(&self) -> Result<Option<JavaInstallation>> {
@@ -80,6 +128,7 @@ impl JavaDetector {
         match JavaProbe::probe(&executable).await {
             Ok(mut installation) => {
                 installation.home = java_home;
+                installation.source = JavaSource::Environment;
 
                 Ok(Some(installation))
             }
@@ -104,7 +153,9 @@ impl JavaDetector {
         let mut handles = Vec::with_capacity(candidates.len());
         for executable in candidates {
             handles.push(tokio::spawn(async move {
-                JavaProbe::probe(&executable).await.ok()
+                let mut installation = JavaProbe::probe(&executable).await.ok()?;
+                installation.source = JavaSource::Path;
+                Some(installation)
             }));
         }
 
@@ -163,13 +214,6 @@ impl JavaDetector {
             "syswow64",
         ];
 
-        if skip_patterns
-            .iter()
-            .any(|pattern| path_str.contains(pattern))
-        {
-            return false;
-        }
-
         #[cfg(windows)]
         if !self.is_32bit_system()
             && let Some(arch) = &installation.arch
@@ -178,18 +222,15 @@ impl JavaDetector {
             return false;
         }
 
-        true
+        !skip_patterns
+            .iter()
+            .any(|pattern| path_str.contains(pattern))
     }
 
     #[cfg(windows)]
     fn is_32bit_system(&self) -> bool {
         std::mem::size_of::<usize>() == 4
     }
-
-    #[cfg(not(windows))]
-    fn is_32bit_system(&self) -> bool {
-        false
-    }
 }
 
 impl Default for JavaDetector {
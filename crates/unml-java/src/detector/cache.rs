@@ -0,0 +1,117 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{JavaInstallation, Result};
+
+static PROBE_CACHE: OnceLock<ProbeCache> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    modified: SystemTime,
+    size: u64,
+    installation: JavaInstallation,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// 探测结果缓存
+///
+/// 以可执行文件的规范路径为键，文件的修改时间或大小变化后视为失效。
+/// 保存时只写入本轮用到的条目，已经消失的安装会被自然淘汰。
+#[derive(Default)]
+pub(crate) struct ProbeCache {
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+    seen: Mutex<HashSet<PathBuf>>,
+}
+
+impl ProbeCache {
+    pub fn global() -> &'static Self {
+        PROBE_CACHE.get_or_init(Self::default)
+    }
+
+    pub fn get(&self, executable: &Path) -> Option<JavaInstallation> {
+        let (key, modified, size) = Self::key(executable)?;
+
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(&key)?;
+        if entry.modified != modified || entry.size != size {
+            return None;
+        }
+
+        let mut installation = entry.installation.clone();
+        installation.executable = executable.to_path_buf();
+        drop(entries);
+
+        self.seen.lock().unwrap().insert(key);
+
+        Some(installation)
+    }
+
+    pub fn insert(&self, executable: &Path, installation: &JavaInstallation) {
+        let Some((key, modified, size)) = Self::key(executable) else {
+            return;
+        };
+
+        self.seen.lock().unwrap().insert(key.clone());
+        self.entries.lock().unwrap().insert(
+            key,
+            CacheEntry {
+                modified,
+                size,
+                installation: installation.clone(),
+            },
+        );
+    }
+
+    /// 从磁盘加载缓存，内存中已有条目时不覆盖；文件不存在或损坏时忽略
+    pub async fn load(&self, path: &Path) {
+        let Ok(data) = tokio::fs::read(path).await else {
+            return;
+        };
+        let Ok(file) = serde_json::from_slice::<CacheFile>(&data) else {
+            return;
+        };
+
+        let mut entries = self.entries.lock().unwrap();
+        for (key, entry) in file.entries {
+            entries.entry(key).or_insert(entry);
+        }
+    }
+
+    /// 保存上次保存以来用到的条目，并丢弃其余条目
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let seen = std::mem::take(&mut *self.seen.lock().unwrap());
+        let file = {
+            let mut entries = self.entries.lock().unwrap();
+            entries.retain(|key, _| seen.contains(key));
+            CacheFile {
+                entries: entries.clone(),
+            }
+        };
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let data = serde_json::to_vec_pretty(&file).map_err(unml_core::JsonError)?;
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, path).await?;
+
+        Ok(())
+    }
+
+    fn key(executable: &Path) -> Option<(PathBuf, SystemTime, u64)> {
+        let canonical = std::fs::canonicalize(executable).ok()?;
+        let metadata = std::fs::metadata(&canonical).ok()?;
+
+        Some((canonical, metadata.modified().ok()?, metadata.len()))
+    }
+}
@@ -1,3 +1,6 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
 use crate::detector::scanner::DirectoryScanner;
 use crate::{JavaInstallation, Result};
 
@@ -15,6 +18,97 @@ impl LinuxDetector {
             }
         }
 
+        installations.extend(Self::detect_from_snap().await);
+        installations.extend(Self::detect_from_user().await);
+
         Ok(installations)
     }
+
+    /// Snap 包中的 JDK，如 `/snap/openjdk/current/jdk`
+    async fn detect_from_snap() -> Vec<JavaInstallation> {
+        let mut installations = Vec::new();
+        for snap in subdirs(Path::new("/snap")) {
+            let current = snap.join("current");
+            if let Ok(found) =
+                DirectoryScanner::scan(current.join("usr").join("lib").join("jvm")).await
+            {
+                installations.extend(found);
+            }
+            if let Ok(found) = DirectoryScanner::scan_recursive(&current, 2).await {
+                installations.extend(found);
+            }
+        }
+
+        installations
+    }
+
+    /// 用户级的 JDK 管理工具与其他启动器下载的运行时
+    async fn detect_from_user() -> Vec<JavaInstallation> {
+        let mut installations = Vec::new();
+        let Some(home) = env::var_os("HOME").map(PathBuf::from) else {
+            return installations;
+        };
+
+        let sdkman = env::var_os("SDKMAN_DIR").map_or_else(|| home.join(".sdkman"), PathBuf::from);
+        let asdf = env::var_os("ASDF_DATA_DIR").map_or_else(|| home.join(".asdf"), PathBuf::from);
+        let gradle =
+            env::var_os("GRADLE_USER_HOME").map_or_else(|| home.join(".gradle"), PathBuf::from);
+        let data = env::var_os("XDG_DATA_HOME")
+            .map_or_else(|| home.join(".local").join("share"), PathBuf::from);
+
+        // 每个子目录就是一个 JDK
+        let mut flat = vec![
+            sdkman.join("candidates").join("java"),
+            asdf.join("installs").join("java"),
+            home.join(".jenv").join("versions"),
+            home.join(".jdks"),
+            data.join("PrismLauncher").join("java"),
+            data.join("PolyMC").join("java"),
+            data.join("multimc").join("java"),
+            home.join("MultiMC").join("java"),
+        ];
+
+        // Gradle 工具链、官方启动器和 HMCL 的运行时目录有额外的嵌套层级
+        let mut nested = vec![
+            gradle.join("jdks"),
+            home.join(".minecraft").join("runtime"),
+            data.join("hmcl").join("java"),
+            home.join(".hmcl").join("java"),
+        ];
+
+        // Flatpak 应用的数据位于 ~/.var/app/<应用 ID>/ 下
+        for app in subdirs(&home.join(".var").join("app")) {
+            for launcher in subdirs(&app.join("data")) {
+                flat.push(launcher.join("java"));
+            }
+            nested.push(app.join(".minecraft").join("runtime"));
+            nested.push(app.join("data").join("hmcl").join("java"));
+        }
+
+        for base in flat {
+            if let Ok(found) = DirectoryScanner::scan(&base).await {
+                installations.extend(found);
+            }
+        }
+        for base in nested {
+            if let Ok(found) = DirectoryScanner::scan_recursive(&base, 3).await {
+                installations.extend(found);
+            }
+        }
+
+        installations
+    }
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir).map_or_else(
+        |_| Vec::new(),
+        |entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|entry| entry.path())
+                .collect()
+        },
+    )
 }
@@ -12,7 +12,8 @@ pub struct WindowsDetector;
 
 impl WindowsDetector {
     pub async fn detect_from_registry() -> Result<Vec<JavaInstallation>> {
-        // Use spawn_blocking for registry operations since RegKey is not Send + Sync
+        // Use spawn_blocking for registry operations since RegKey is not Send +
+        // Sync
         let java_homes = tokio::task::spawn_blocking(|| {
             let mut homes = Vec::new();
 
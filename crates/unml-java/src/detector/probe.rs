@@ -1,8 +1,10 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use tokio::process::Command;
 
-use crate::{Error, JavaInstallation, Result};
+use super::cache::ProbeCache;
+use crate::{Error, JavaInstallation, JavaSource, Result};
 
 pub struct JavaProbe;
 
@@ -12,31 +14,142 @@ impl JavaProbe {
             return Err(Error::JavaNotFound);
         }
 
-        let output = Command::new(executable).arg("-version").output().await?;
+        let cache = ProbeCache::global();
+        if let Some(installation) = cache.get(executable) {
+            return Ok(installation);
+        }
 
+        let output = Command::new(executable)
+            .args(["-XshowSettings:properties", "-version"])
+            .output()
+            .await?;
         let stderr = String::from_utf8_lossy(&output.stderr);
 
-        let version = Self::parse_version(&stderr)?;
+        // Java 6 不认识 -XshowSettings，会直接报错退出
+        let mut installation = match Self::parse_properties(&stderr) {
+            Some(properties) => Self::from_properties(executable, &properties, &stderr)?,
+            None => {
+                let output = Command::new(executable).arg("-version").output().await?;
+                Self::from_banner(executable, &String::from_utf8_lossy(&output.stderr))?
+            }
+        };
+
+        if !installation.home.exists() {
+            installation.home = Self::home_from_executable(executable)?;
+        }
+
+        cache.insert(executable, &installation);
+
+        Ok(installation)
+    }
+
+    /// 解析 `-XshowSettings:properties` 的输出
+    ///
+    /// 每个属性占一行并缩进 4
+    /// 个空格；多值属性的后续值缩进更多，这里只保留第一个值。
+    pub fn parse_properties(output: &str) -> Option<HashMap<String, String>> {
+        let mut lines = output.lines();
+        lines.find(|line| line.trim_start().starts_with("Property settings:"))?;
+
+        let mut properties = HashMap::new();
+        for line in lines {
+            if line.trim().is_empty() {
+                break;
+            }
+
+            let Some(entry) = line.strip_prefix("    ") else {
+                continue;
+            };
+            if entry.starts_with(' ') {
+                continue;
+            }
+
+            if let Some((key, value)) = entry.split_once(" = ") {
+                properties.insert(key.trim().to_owned(), value.trim().to_owned());
+            } else if let Some(key) = entry.strip_suffix(" =") {
+                properties.insert(key.trim().to_owned(), String::new());
+            }
+        }
+
+        properties
+            .contains_key("java.version")
+            .then_some(properties)
+    }
+
+    fn from_properties(
+        executable: &Path,
+        properties: &HashMap<String, String>,
+        output: &str,
+    ) -> Result<JavaInstallation> {
+        let property = |key: &str| {
+            properties
+                .get(key)
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+
+        let version = property("java.version").ok_or(Error::VersionParseFailed)?;
         let major_version = Self::extract_major_version(&version)?;
-        let vendor = Self::parse_vendor(&stderr);
-        let arch = Self::parse_arch(&stderr);
 
-        let home = executable
-            .parent()
-            .and_then(|p| p.parent())
-            .ok_or(Error::InvalidJavaPath)?
-            .to_path_buf();
+        let arch = property("os.arch")
+            .map(|arch| Self::normalize_arch(&arch, property("sun.arch.data.model").as_deref()))
+            .or_else(|| Self::parse_arch(output));
+
+        let home = match property("java.home") {
+            Some(home) => PathBuf::from(home),
+            None => Self::home_from_executable(executable)?,
+        };
 
         Ok(JavaInstallation {
             executable: executable.to_path_buf(),
             version,
             major_version,
-            vendor,
+            vendor: property("java.vendor").or_else(|| Self::parse_vendor(output)),
+            vm_name: property("java.vm.name"),
             home,
             arch,
+            source: JavaSource::System,
+        })
+    }
+
+    /// 旧版本的回退：解析 `java -version` 的横幅
+    fn from_banner(executable: &Path, output: &str) -> Result<JavaInstallation> {
+        let version = Self::parse_version(output)?;
+        let major_version = Self::extract_major_version(&version)?;
+
+        Ok(JavaInstallation {
+            executable: executable.to_path_buf(),
+            version,
+            major_version,
+            vendor: Self::parse_vendor(output),
+            vm_name: None,
+            home: Self::home_from_executable(executable)?,
+            arch: Self::parse_arch(output),
+            source: JavaSource::System,
         })
     }
 
+    fn home_from_executable(executable: &Path) -> Result<PathBuf> {
+        executable
+            .parent()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+            .ok_or(Error::InvalidJavaPath)
+    }
+
+    /// 统一 `os.arch` 的各种写法，与横幅解析的结果保持一致
+    fn normalize_arch(os_arch: &str, data_model: Option<&str>) -> String {
+        match os_arch {
+            "amd64" | "x86_64" => "x64".to_owned(),
+            "aarch64" | "arm64" => "aarch64".to_owned(),
+            "x86" | "i386" | "i486" | "i586" | "i686" if data_model == Some("64") => {
+                "x64".to_owned()
+            }
+            "x86" | "i386" | "i486" | "i586" | "i686" => "x86".to_owned(),
+            other => other.to_owned(),
+        }
+    }
+
     fn parse_version(output: &str) -> Result<String> {
         for line in output.lines() {
             if line.contains("version")
@@ -53,13 +166,13 @@ impl JavaProbe {
     fn extract_major_version(version: &str) -> Result<u32> {
         // "1.8.0_292" -> 8
         // "17.0.1" -> 17
+        // "21-ea" -> 21
         let stripped = version.strip_prefix("1.").unwrap_or(version);
-        let parts: Vec<&str> = stripped.split('.').collect();
-        if !parts.is_empty() {
-            return parts[0].parse().map_err(|_| Error::VersionParseFailed);
-        }
-
-        Err(Error::VersionParseFailed)
+        stripped
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|major| major.parse().ok())
+            .ok_or(Error::VersionParseFailed)
     }
 
     fn parse_vendor(output: &str) -> Option<String> {
@@ -92,3 +205,119 @@ impl JavaProbe {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 解析 `java -XshowSettings:properties -version` 的输出
+    fn probe_fixture(output: &str) -> JavaInstallation {
+        let properties = JavaProbe::parse_properties(output).expect("property settings");
+        JavaProbe::from_properties(Path::new("/fixture/bin/java"), &properties, output)
+            .expect("installation")
+    }
+
+    #[test]
+    fn temurin() {
+        let java = probe_fixture(include_str!(
+            "../../tests/fixtures/properties/temurin-17-linux-x64.txt"
+        ));
+        assert_eq!(java.vendor.as_deref(), Some("Eclipse Adoptium"));
+        assert_eq!(java.version, "17.0.10");
+        assert_eq!(java.major_version, 17);
+        assert_eq!(java.arch.as_deref(), Some("x64"));
+        assert_eq!(java.vm_name.as_deref(), Some("OpenJDK 64-Bit Server VM"));
+        assert_eq!(
+            java.home,
+            PathBuf::from("/usr/lib/jvm/temurin-17-jdk-amd64")
+        );
+    }
+
+    #[test]
+    fn zulu() {
+        let java = probe_fixture(include_str!(
+            "../../tests/fixtures/properties/zulu-8-windows-x64.txt"
+        ));
+        assert_eq!(java.vendor.as_deref(), Some("Azul Systems, Inc."));
+        assert_eq!(java.version, "1.8.0_402");
+        assert_eq!(java.major_version, 8);
+        assert_eq!(java.arch.as_deref(), Some("x64"));
+        assert_eq!(
+            java.home,
+            PathBuf::from(r"C:\Program Files\Zulu\zulu-8\jre")
+        );
+    }
+
+    #[test]
+    fn graalvm() {
+        let java = probe_fixture(include_str!(
+            "../../tests/fixtures/properties/graalvm-21-macos-aarch64.txt"
+        ));
+        assert_eq!(java.vendor.as_deref(), Some("GraalVM Community"));
+        assert_eq!(java.version, "21.0.2");
+        assert_eq!(java.major_version, 21);
+        assert_eq!(java.arch.as_deref(), Some("aarch64"));
+    }
+
+    #[test]
+    fn openj9() {
+        let java = probe_fixture(include_str!(
+            "../../tests/fixtures/properties/openj9-17-linux-x64.txt"
+        ));
+        assert_eq!(java.vendor.as_deref(), Some("IBM Corporation"));
+        assert_eq!(java.version, "17.0.9");
+        assert_eq!(java.major_version, 17);
+        assert_eq!(java.arch.as_deref(), Some("x64"));
+        assert_eq!(java.vm_name.as_deref(), Some("Eclipse OpenJ9 VM"));
+        assert_eq!(java.home, PathBuf::from("/opt/semeru/jdk-17.0.9+9"));
+    }
+
+    #[test]
+    fn oracle_8_32_bit() {
+        let java = probe_fixture(include_str!(
+            "../../tests/fixtures/properties/oracle-8-windows-x86.txt"
+        ));
+        assert_eq!(java.vendor.as_deref(), Some("Oracle Corporation"));
+        assert_eq!(java.version, "1.8.0_401");
+        assert_eq!(java.major_version, 8);
+        assert_eq!(java.arch.as_deref(), Some("x86"));
+        assert_eq!(java.vm_name.as_deref(), Some("Java HotSpot(TM) Client VM"));
+    }
+
+    #[test]
+    fn microsoft() {
+        let java = probe_fixture(include_str!(
+            "../../tests/fixtures/properties/microsoft-21-windows-aarch64.txt"
+        ));
+        assert_eq!(java.vendor.as_deref(), Some("Microsoft"));
+        assert_eq!(java.version, "21.0.2");
+        assert_eq!(java.major_version, 21);
+        assert_eq!(java.arch.as_deref(), Some("aarch64"));
+    }
+
+    #[test]
+    fn continuation_lines_are_skipped() {
+        let properties = JavaProbe::parse_properties(include_str!(
+            "../../tests/fixtures/properties/openj9-17-linux-x64.txt"
+        ))
+        .unwrap();
+        assert_eq!(properties["java.library.path"], "/usr/lib64");
+        assert!(!properties.contains_key("OpenJ9   - 8ad3b6d"));
+        assert_eq!(properties["java.class.path"], "");
+    }
+
+    #[test]
+    fn java_6_falls_back_to_banner() {
+        let output = "Unrecognized option: -XshowSettings:properties\n\
+                      Error: Could not create the Java Virtual Machine.\n";
+        assert!(JavaProbe::parse_properties(output).is_none());
+
+        let banner = "java version \"1.6.0_45\"\n\
+                      Java(TM) SE Runtime Environment (build 1.6.0_45-b06)\n\
+                      Java HotSpot(TM) 64-Bit Server VM (build 20.45-b01, mixed mode)\n";
+        let java = JavaProbe::from_banner(Path::new("/jdk6/bin/java"), banner).unwrap();
+        assert_eq!(java.version, "1.6.0_45");
+        assert_eq!(java.major_version, 6);
+        assert_eq!(java.arch.as_deref(), Some("x64"));
+    }
+}
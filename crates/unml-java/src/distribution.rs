@@ -0,0 +1,434 @@
+mod adoptium;
+mod zulu;
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+pub use adoptium::AdoptiumProvider;
+use async_trait::async_trait;
+use futures::StreamExt;
+use sha1::Sha1;
+use sha2::digest::DynDigest;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use unml_core::Checksum;
+pub use zulu::ZuluProvider;
+
+use crate::detector::probe::JavaProbe;
+use crate::runtime::VERSION_MARKER;
+use crate::{Error, JavaInstallation, JavaSource, Result, http_client};
+
+/// 发行版压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// 当前平台发行版常用的压缩包格式
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            Self::Zip
+        } else {
+            Self::TarGz
+        }
+    }
+}
+
+/// 某个发行版的一个可下载包
+#[derive(Debug, Clone)]
+pub struct JavaPackage {
+    /// 完整版本号，如 `17.0.10+7`
+    pub version: String,
+    pub major_version: u32,
+    pub url: String,
+    pub checksum: Option<Checksum>,
+    pub archive: ArchiveKind,
+}
+
+/// 第三方 JDK 发行版来源
+#[async_trait]
+pub trait JavaDistributionProvider: Send + Sync {
+    /// 发行版标识，同时用作安装目录的前缀
+    fn id(&self) -> &'static str;
+
+    /// 当前系统与架构可用的主版本号
+    async fn available_versions(&self) -> Result<Vec<u32>>;
+
+    /// 获取指定主版本的最新包
+    async fn resolve(&self, major: u32) -> Result<JavaPackage>;
+}
+
+/// 下载、校验并解压第三方发行版到启动器管理的运行时目录
+///
+/// 与 [`crate::RuntimeInstaller`] 使用同一目录时，安装结果会出现在其
+/// `installed()` 与 [`crate::JavaManager::refresh`] 中。
+pub struct DistributionInstaller {
+    root: PathBuf,
+}
+
+impl DistributionInstaller {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub async fn install(
+        &self,
+        provider: &dyn JavaDistributionProvider,
+        major: u32,
+    ) -> Result<JavaInstallation> {
+        let package = provider.resolve(major).await?;
+
+        let name = format!("{}-{}", provider.id(), sanitize(&package.version));
+        let dest = self.root.join(&name);
+        let archive = self.root.join(format!("{name}.download"));
+
+        tokio::fs::create_dir_all(&self.root).await?;
+        download(&package.url, &archive, package.checksum.as_ref()).await?;
+
+        // 先解压到临时目录，成功后再移动，避免留下半个 JDK
+        let staging = self.root.join(format!("{name}.staging"));
+        if tokio::fs::try_exists(&staging).await? {
+            tokio::fs::remove_dir_all(&staging).await?;
+        }
+
+        let kind = package.archive;
+        let (archive_path, staging_path) = (archive.clone(), staging.clone());
+        tokio::task::spawn_blocking(move || extract(kind, &archive_path, &staging_path))
+            .await
+            .map_err(|e| Error::Extract(e.to_string()))??;
+        tokio::fs::remove_file(&archive).await?;
+        tokio::fs::write(staging.join(VERSION_MARKER), &package.version).await?;
+
+        if tokio::fs::try_exists(&dest).await? {
+            tokio::fs::remove_dir_all(&dest).await?;
+        }
+        tokio::fs::rename(&staging, &dest).await?;
+
+        let home = find_java_home(&dest).ok_or(Error::InvalidJavaPath)?;
+        let executable = home.join("bin").join(JavaInstallation::executable_name());
+
+        let mut installation = JavaProbe::probe(&executable).await?;
+        installation.home = home;
+        installation.source = JavaSource::Managed;
+
+        Ok(installation)
+    }
+}
+
+/// 当前系统与架构在各发行版 API 中的通用名称
+pub(crate) fn host_os() -> Result<&'static str> {
+    match std::env::consts::OS {
+        "windows" => Ok("windows"),
+        "macos" => Ok("mac"),
+        "linux" => Ok("linux"),
+        _ => Err(Error::UnsupportedPlatform),
+    }
+}
+
+pub(crate) fn host_arch() -> Result<&'static str> {
+    match std::env::consts::ARCH {
+        "x86_64" => Ok("x64"),
+        "x86" => Ok("x86"),
+        "aarch64" => Ok("aarch64"),
+        _ => Err(Error::UnsupportedPlatform),
+    }
+}
+
+pub(crate) async fn fetch_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T> {
+    let body = fetch_bytes(url).await?;
+
+    Ok(serde_json::from_slice(&body).map_err(unml_core::JsonError)?)
+}
+
+pub(crate) async fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
+    let body = http_client()
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| unml_core::HttpError(e.to_string()))?
+        .bytes()
+        .await
+        .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+    Ok(body.to_vec())
+}
+
+/// 下载并校验压缩包，没有校验和时拒绝安装
+async fn download(url: &str, dest: &Path, checksum: Option<&Checksum>) -> Result<()> {
+    let (mut hasher, expected): (Box<dyn DynDigest + Send>, &str) = match checksum {
+        Some(Checksum::Sha256(expected)) => (Box::new(Sha256::new()), expected),
+        Some(Checksum::Sha1(expected)) => (Box::new(Sha1::new()), expected),
+        None => return Err(Error::MissingChecksum(url.to_owned())),
+    };
+
+    let response = http_client()
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+    let mut file = tokio::fs::File::create(dest).await?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| unml_core::HttpError(e.to_string()))?;
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    drop(file);
+
+    let actual: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if !actual.eq_ignore_ascii_case(expected) {
+        let _ = tokio::fs::remove_file(dest).await;
+        return Err(unml_core::ChecksumError {
+            expected: expected.to_owned(),
+            actual,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+fn extract(kind: ArchiveKind, archive: &Path, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+    let file = File::open(archive)?;
+
+    match kind {
+        ArchiveKind::TarGz => {
+            let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+            archive.set_preserve_permissions(true);
+            archive.unpack(dest)?;
+        }
+        ArchiveKind::Zip => {
+            zip::ZipArchive::new(file)
+                .and_then(|mut archive| archive.extract(dest))
+                .map_err(|e| Error::Extract(e.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 压缩包通常只有一个顶层目录；macOS 的包还要进入 `Contents/Home`
+pub(crate) fn find_java_home(dir: &Path) -> Option<PathBuf> {
+    let executable = PathBuf::from("bin").join(JavaInstallation::executable_name());
+
+    let mut candidates = vec![dir.to_path_buf()];
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            candidates.push(entry.path().join("Contents").join("Home"));
+            candidates.push(entry.path());
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find(|home| home.join(&executable).exists())
+}
+
+fn sanitize(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::RuntimeInstaller;
+
+    /// 在本地端口上提供 HTTP 服务，`handler` 按请求路径返回响应体，`None`
+    /// 时返回 404
+    async fn serve(handler: fn(&str) -> Option<Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split(' ').nth(1).unwrap_or_default();
+
+                let (status, body) = match handler(path) {
+                    Some(body) => ("200 OK", body),
+                    None => ("404 Not Found", Vec::new()),
+                };
+                let head = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).await.unwrap();
+                stream.write_all(&body).await.unwrap();
+            }
+        });
+        base
+    }
+
+    /// 一个只含 `bin/java` 脚本的 JDK 压缩包，脚本输出 Temurin 17 的属性
+    fn fake_jdk() -> Vec<u8> {
+        let properties = include_str!("../tests/fixtures/properties/temurin-17-linux-x64.txt");
+        let script = format!("#!/bin/sh\ncat >&2 <<'EOF'\n{properties}EOF\n");
+
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::fast(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(script.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append_data(&mut header, "jdk-17.0.10+7-jre/bin/java", script.as_bytes())
+            .unwrap();
+        let mut gz = tar.into_inner().unwrap();
+        gz.flush().unwrap();
+        gz.finish().unwrap()
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("unml-java-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        root
+    }
+
+    struct LocalProvider {
+        url: String,
+        checksum: Option<Checksum>,
+    }
+
+    #[async_trait]
+    impl JavaDistributionProvider for LocalProvider {
+        fn id(&self) -> &'static str {
+            "local"
+        }
+
+        async fn available_versions(&self) -> Result<Vec<u32>> {
+            Ok(vec![17])
+        }
+
+        async fn resolve(&self, major: u32) -> Result<JavaPackage> {
+            Ok(JavaPackage {
+                version: "17.0.10+7".to_owned(),
+                major_version: major,
+                url: self.url.clone(),
+                checksum: self.checksum.clone(),
+                archive: ArchiveKind::TarGz,
+            })
+        }
+    }
+
+    async fn local_provider(checksum: impl FnOnce(&[u8]) -> Option<Checksum>) -> LocalProvider {
+        let base = serve(|path| (path == "/jdk.tar.gz").then(fake_jdk)).await;
+        LocalProvider {
+            url: format!("{base}/jdk.tar.gz"),
+            checksum: checksum(&fake_jdk()),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn install_is_found_by_runtime_installer() {
+        let root = temp_root("install");
+        let provider =
+            local_provider(|data| Some(Checksum::Sha256(format!("{:x}", Sha256::digest(data)))))
+                .await;
+
+        let installation = DistributionInstaller::new(&root)
+            .install(&provider, 17)
+            .await
+            .unwrap();
+        assert_eq!(installation.major_version, 17);
+        assert_eq!(installation.source, JavaSource::Managed);
+
+        let dir = root.join("local-17.0.10_7");
+        assert_eq!(
+            std::fs::read_to_string(dir.join(VERSION_MARKER)).unwrap(),
+            "17.0.10+7"
+        );
+        assert_eq!(installation.home, dir.join("jdk-17.0.10+7-jre"));
+
+        let installed = RuntimeInstaller::new(&root).installed().await.unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].executable, installation.executable);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn checksum_mismatch_is_rejected() {
+        let root = temp_root("mismatch");
+        let provider = local_provider(|_| Some(Checksum::Sha256("00".repeat(32)))).await;
+
+        let result = DistributionInstaller::new(&root)
+            .install(&provider, 17)
+            .await;
+        assert!(matches!(result, Err(Error::Checksum(_))));
+        assert!(!root.join("local-17.0.10_7").exists());
+        assert!(!root.join("local-17.0.10_7.download").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_checksum_is_rejected() {
+        let root = temp_root("missing");
+        let provider = local_provider(|_| None).await;
+
+        let result = DistributionInstaller::new(&root)
+            .install(&provider, 17)
+            .await;
+        assert!(matches!(result, Err(Error::MissingChecksum(_))));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[tokio::test]
+    async fn zulu_falls_back_to_jdk() {
+        let base = serve(|path| {
+            let body = if path.contains("java_package_type=jre") {
+                "[]".to_owned()
+            } else if path.contains("java_package_type=jdk") {
+                r#"[{"package_uuid": "abc", "java_version": [8, 0, 402], "download_url": "https://cdn.azul.com/zulu8-jdk.tar.gz"}]"#.to_owned()
+            } else if path.ends_with("/packages/abc") {
+                format!(r#"{{"sha256_hash": "{}"}}"#, "ab".repeat(32))
+            } else {
+                return None;
+            };
+            Some(body.into_bytes())
+        })
+        .await;
+
+        let package = ZuluProvider::with_base_url(base).resolve(8).await.unwrap();
+        assert_eq!(package.version, "8.0.402");
+        assert_eq!(package.url, "https://cdn.azul.com/zulu8-jdk.tar.gz");
+        assert!(matches!(package.checksum, Some(Checksum::Sha256(_))));
+    }
+}
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use futures::future;
+use serde::Deserialize;
+use unml_core::Checksum;
+
+use super::{ArchiveKind, JavaDistributionProvider, JavaPackage, fetch_json, host_arch, host_os};
+use crate::{Error, Result};
+
+#[derive(Debug, Deserialize)]
+struct AvailableReleases {
+    available_releases: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    binary: Binary,
+    version: AssetVersion,
+}
+
+#[derive(Debug, Deserialize)]
+struct Binary {
+    package: Package,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+    link: String,
+    checksum: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetVersion {
+    semver: String,
+    major: u32,
+}
+
+/// Eclipse Adoptium (Temurin)
+pub struct AdoptiumProvider {
+    base_url: String,
+}
+
+impl AdoptiumProvider {
+    pub fn new() -> Self {
+        Self::with_base_url("https://api.adoptium.net")
+    }
+
+    /// 使用自定义 API 地址（镜像或本地测试服务）
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+        }
+    }
+
+    async fn latest(&self, major: u32, image_type: &str) -> Result<Vec<Asset>> {
+        let arch = match host_arch()? {
+            "x86" => "x32",
+            arch => arch,
+        };
+
+        fetch_json(&format!(
+            "{}/v3/assets/latest/{major}/hotspot?os={}&architecture={arch}&image_type={image_type}&vendor=eclipse",
+            self.base_url,
+            host_os()?,
+        ))
+        .await
+    }
+
+    /// 部分平台没有 JRE 包，退回 JDK
+    async fn assets(&self, major: u32) -> Result<Vec<Asset>> {
+        let assets = self.latest(major, "jre").await?;
+        if !assets.is_empty() {
+            return Ok(assets);
+        }
+        self.latest(major, "jdk").await
+    }
+}
+
+#[async_trait]
+impl JavaDistributionProvider for AdoptiumProvider {
+    fn id(&self) -> &'static str {
+        "temurin"
+    }
+
+    async fn available_versions(&self) -> Result<Vec<u32>> {
+        let releases: AvailableReleases =
+            fetch_json(&format!("{}/v3/info/available_releases", self.base_url)).await?;
+
+        // 发布列表不区分平台，只保留当前系统与架构有包的版本
+        let assets = future::try_join_all(
+            releases
+                .available_releases
+                .iter()
+                .map(|&major| self.assets(major)),
+        )
+        .await?;
+        let mut versions: Vec<u32> = releases
+            .available_releases
+            .into_iter()
+            .zip(assets)
+            .filter(|(_, assets)| !assets.is_empty())
+            .map(|(major, _)| major)
+            .collect();
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+
+        Ok(versions)
+    }
+
+    async fn resolve(&self, major: u32) -> Result<JavaPackage> {
+        let asset = self
+            .assets(major)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::RuntimeUnavailable(format!("{} {major}", self.id())))?;
+        let archive = if asset.binary.package.name.ends_with(".zip") {
+            ArchiveKind::Zip
+        } else {
+            ArchiveKind::TarGz
+        };
+
+        Ok(JavaPackage {
+            version: asset.version.semver,
+            major_version: asset.version.major,
+            url: asset.binary.package.link,
+            checksum: asset.binary.package.checksum.map(Checksum::Sha256),
+            archive,
+        })
+    }
+}
+
+impl Default for AdoptiumProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
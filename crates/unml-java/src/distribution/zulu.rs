@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use unml_core::Checksum;
+
+use super::{ArchiveKind, JavaDistributionProvider, JavaPackage, fetch_json, host_arch, host_os};
+use crate::{Error, Result};
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    package_uuid: String,
+    java_version: Vec<u32>,
+    download_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageDetail {
+    sha256_hash: Option<String>,
+}
+
+/// Azul Zulu
+pub struct ZuluProvider {
+    base_url: String,
+}
+
+impl ZuluProvider {
+    pub fn new() -> Self {
+        Self::with_base_url("https://api.azul.com")
+    }
+
+    /// 使用自定义 API 地址（镜像或本地测试服务）
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+        }
+    }
+
+    /// `package_type` 为 `jre` 或 `jdk`
+    async fn packages(
+        &self,
+        java_version: Option<u32>,
+        package_type: &str,
+    ) -> Result<Vec<Package>> {
+        let os = match host_os()? {
+            "mac" => "macos",
+            os => os,
+        };
+        let archive_type = match ArchiveKind::native() {
+            ArchiveKind::Zip => "zip",
+            ArchiveKind::TarGz => "tar.gz",
+        };
+
+        let mut url = format!(
+            "{}/metadata/v1/zulu/packages/?os={os}&arch={}&archive_type={archive_type}&java_package_type={package_type}&javafx_bundled=false&latest=true&release_status=ga&availability_types=CA&page_size=1000",
+            self.base_url,
+            host_arch()?,
+        );
+        if let Some(major) = java_version {
+            url.push_str(&format!("&java_version={major}"));
+        }
+
+        fetch_json(&url).await
+    }
+}
+
+#[async_trait]
+impl JavaDistributionProvider for ZuluProvider {
+    fn id(&self) -> &'static str {
+        "zulu"
+    }
+
+    async fn available_versions(&self) -> Result<Vec<u32>> {
+        let mut versions: Vec<u32> = self
+            .packages(None, "jre")
+            .await?
+            .into_iter()
+            .chain(self.packages(None, "jdk").await?)
+            .filter_map(|package| package.java_version.first().copied())
+            .collect();
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+        versions.dedup();
+
+        Ok(versions)
+    }
+
+    async fn resolve(&self, major: u32) -> Result<JavaPackage> {
+        // 部分平台（如 macOS aarch64 上的旧版本）没有 JRE 包，退回 JDK
+        let mut packages = self.packages(Some(major), "jre").await?;
+        if packages.is_empty() {
+            packages = self.packages(Some(major), "jdk").await?;
+        }
+
+        let package = packages
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::RuntimeUnavailable(format!("{} {major}", self.id())))?;
+
+        // 列表接口不返回校验和，需要单独查询
+        let detail: PackageDetail = fetch_json(&format!(
+            "{}/metadata/v1/zulu/packages/{}",
+            self.base_url, package.package_uuid
+        ))
+        .await?;
+
+        let version = package
+            .java_version
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+        let archive = if package.download_url.ends_with(".zip") {
+            ArchiveKind::Zip
+        } else {
+            ArchiveKind::TarGz
+        };
+
+        Ok(JavaPackage {
+            version,
+            major_version: major,
+            url: package.download_url,
+            checksum: detail.sha256_hash.map(Checksum::Sha256),
+            archive,
+        })
+    }
+}
+
+impl Default for ZuluProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
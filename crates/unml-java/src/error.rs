@@ -1,12 +1,22 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
+use crate::{JavaRequirement, RejectedJava};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Java not found")]
     JavaNotFound,
 
-    #[error("No suitable Java installation found (required version: {0})")]
-    NoSuitableJava(u32),
+    #[error("No suitable Java installation found (required: {requirement})")]
+    NoSuitableJava {
+        requirement: JavaRequirement,
+        rejected: Vec<RejectedJava>,
+    },
+
+    #[error("The Java pinned for this instance is not available: {}", .0.display())]
+    PinnedJavaUnavailable(PathBuf),
 
     #[error("Failed to parse Java version")]
     VersionParseFailed,
@@ -14,8 +24,29 @@ pub enum Error {
     #[error("Invalid Java installation path")]
     InvalidJavaPath,
 
+    #[error("Java runtime {0} is not available for this platform")]
+    RuntimeUnavailable(String),
+
+    #[error("Managed Java runtimes are not available for this platform")]
+    UnsupportedPlatform,
+
+    #[error("No checksum available for {0}")]
+    MissingChecksum(String),
+
+    #[error("Failed to extract Java archive: {0}")]
+    Extract(String),
+
     #[error(transparent)]
     Io(#[from] unml_core::IoError),
+
+    #[error(transparent)]
+    Http(#[from] unml_core::HttpError),
+
+    #[error(transparent)]
+    Json(#[from] unml_core::JsonError),
+
+    #[error(transparent)]
+    Checksum(#[from] unml_core::ChecksumError),
 }
 
 impl From<std::io::Error> for Error {
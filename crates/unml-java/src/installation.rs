@@ -11,8 +11,32 @@ pub struct JavaInstallation {
     pub version: String,
     pub major_version: u32,
     pub vendor: Option<String>,
+    /// `java.vm.name`，如 `OpenJDK 64-Bit Server VM`、`Eclipse OpenJ9 VM`
+    #[serde(default)]
+    pub vm_name: Option<String>,
     pub home: PathBuf,
     pub arch: Option<String>,
+    #[serde(default)]
+    pub source: JavaSource,
+}
+
+/// Java 安装的来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JavaSource {
+    /// `JAVA_HOME` 环境变量
+    Environment,
+    /// `PATH` 环境变量
+    Path,
+    /// 系统或常见 JDK 管理工具的安装目录
+    #[default]
+    System,
+    /// 用户配置的额外扫描目录
+    SearchPath,
+    /// 用户手动添加
+    Custom,
+    /// 启动器下载并管理的运行时
+    Managed,
 }
 
 impl JavaInstallation {
@@ -20,6 +44,10 @@ impl JavaInstallation {
         self.major_version >= required.major
     }
 
+    pub fn is_managed(&self) -> bool {
+        self.source == JavaSource::Managed
+    }
+
     pub fn executable_name() -> &'static str {
         if cfg!(windows) { "java.exe" } else { "java" }
     }
@@ -40,3 +68,34 @@ impl JavaInstallation {
         from_home
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample() -> serde_json::Value {
+        json!({
+            "executable": "/opt/runtime/bin/java",
+            "version": "17.0.8",
+            "major_version": 17,
+            "vendor": "Microsoft",
+            "home": "/opt/runtime",
+            "arch": "amd64",
+        })
+    }
+
+    #[test]
+    fn source_round_trips() {
+        let mut installation: JavaInstallation = serde_json::from_value(sample()).unwrap();
+        assert_eq!(installation.source, JavaSource::System);
+        installation.source = JavaSource::Custom;
+
+        let json = serde_json::to_value(&installation).unwrap();
+        assert_eq!(json["source"], "custom");
+
+        let parsed: JavaInstallation = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.source, JavaSource::Custom);
+    }
+}
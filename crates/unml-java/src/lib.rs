@@ -1,11 +1,36 @@
 mod detector;
+mod distribution;
 mod error;
 mod installation;
 mod manager;
+mod requirement;
+mod runtime;
+mod settings;
 mod version;
 
+use std::sync::OnceLock;
+
 pub use detector::JavaDetector;
+pub use distribution::{
+    AdoptiumProvider, ArchiveKind, DistributionInstaller, JavaDistributionProvider, JavaPackage,
+    ZuluProvider,
+};
 pub use error::{Error, Result};
-pub use installation::JavaInstallation;
+pub use installation::{JavaInstallation, JavaSource};
 pub use manager::JavaManager;
+pub use requirement::{JavaRequirement, RejectReason, RejectedJava};
+use reqwest::Client;
+pub use runtime::{RuntimeInstaller, components};
+pub use settings::JavaSettings;
 pub use version::JavaVersion;
+
+static HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
+
+pub(crate) fn http_client() -> &'static Client {
+    HTTP_CLIENT.get_or_init(|| {
+        Client::builder()
+            .user_agent("UNML/0.1.0")
+            .build()
+            .expect("Failed to create HTTP client")
+    })
+}
@@ -1,11 +1,31 @@
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use tokio::sync::RwLock;
+use unml_core::Instance;
 
-use crate::{Error, JavaDetector, JavaInstallation, JavaVersion, Result};
+use crate::detector::cache::ProbeCache;
+use crate::detector::probe::JavaProbe;
+use crate::detector::scanner::DirectoryScanner;
+use crate::{
+    DistributionInstaller, Error, JavaDetector, JavaDistributionProvider, JavaInstallation,
+    JavaRequirement, JavaSettings, JavaSource, RejectReason, RejectedJava, Result,
+    RuntimeInstaller,
+};
+
+/// 手动扫描文件夹时的最大深度
+const FOLDER_SCAN_DEPTH: u32 = 5;
 
 pub struct JavaManager {
     detector: JavaDetector,
+    runtimes: Option<RuntimeInstaller>,
+    /// 发行商偏好，靠前的优先，按子串匹配且不区分大小写
+    preferred_vendors: Vec<String>,
+    /// 探测结果缓存文件
+    cache_file: Option<PathBuf>,
+    settings: Arc<RwLock<JavaSettings>>,
+    settings_file: Option<PathBuf>,
     cache: Arc<RwLock<Option<Vec<JavaInstallation>>>>,
 }
 
@@ -13,10 +33,51 @@ impl JavaManager {
     pub fn new() -> Self {
         Self {
             detector: JavaDetector::new(),
+            runtimes: None,
+            preferred_vendors: Vec::new(),
+            cache_file: None,
+            settings: Arc::new(RwLock::new(JavaSettings::default())),
+            settings_file: None,
             cache: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// 设置启动器管理的运行时目录，启用官方运行时的下载
+    pub fn with_runtime_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.runtimes = Some(RuntimeInstaller::new(dir));
+        self
+    }
+
+    /// 额外扫描的 Java 目录
+    pub fn with_search_paths(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.detector = self.detector.with_search_paths(paths);
+        self
+    }
+
+    /// 把探测结果持久化到指定文件，启动时只重新探测有变化的安装
+    pub fn with_cache_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_file = Some(path.into());
+        self
+    }
+
+    /// 用户配置（手动添加、隐藏、默认与固定）的保存位置
+    pub fn with_settings_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.settings_file = Some(path.into());
+        self
+    }
+
+    pub fn with_preferred_vendors<I, S>(mut self, vendors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.preferred_vendors = vendors
+            .into_iter()
+            .map(|vendor| vendor.into().to_lowercase())
+            .collect();
+        self
+    }
+
     pub async fn get_installations(&self) -> Result<Vec<JavaInstallation>> {
         let cache = self.cache.read().await;
         if let Some(ref installations) = *cache {
@@ -28,7 +89,34 @@ impl JavaManager {
     }
 
     pub async fn refresh(&self) -> Result<Vec<JavaInstallation>> {
-        let installations = self.detector.detect().await?;
+        if let Some(path) = &self.cache_file {
+            ProbeCache::global().load(path).await;
+        }
+        if let Some(path) = &self.settings_file {
+            *self.settings.write().await = JavaSettings::load(path).await?;
+        }
+
+        let mut installations = self.detector.detect().await?;
+
+        if let Some(runtimes) = &self.runtimes {
+            installations.extend(runtimes.installed().await?);
+        }
+
+        let custom = self.settings.read().await.custom.clone();
+        for executable in custom {
+            if contains(&installations, &executable) {
+                continue;
+            }
+            // 失效的手动路径保留在配置中，等待用户处理
+            if let Ok(mut installation) = JavaProbe::probe(&executable).await {
+                installation.source = JavaSource::Custom;
+                installations.push(installation);
+            }
+        }
+
+        if let Some(path) = &self.cache_file {
+            ProbeCache::global().save(path).await?;
+        }
 
         let mut cache = self.cache.write().await;
         *cache = Some(installations.clone());
@@ -36,13 +124,235 @@ impl JavaManager {
         Ok(installations)
     }
 
-    pub async fn find_best(&self, required: JavaVersion) -> Result<JavaInstallation> {
-        let installations = self.get_installations().await?;
+    /// 按优先级排序满足要求的安装，同时返回被排除的安装及原因
+    ///
+    /// 排序依次为：全局默认、与首选版本完全一致、发行商偏好、64
+    /// 位、补丁版本更新。
+    pub async fn candidates(
+        &self,
+        requirement: &JavaRequirement,
+    ) -> Result<(Vec<JavaInstallation>, Vec<RejectedJava>)> {
+        let settings = self.settings.read().await.clone();
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
 
-        installations
+        for installation in self.get_installations().await? {
+            if settings.is_hidden(&installation.executable) {
+                rejected.push(RejectedJava {
+                    installation,
+                    reason: RejectReason::Hidden,
+                });
+                continue;
+            }
+
+            match requirement.check(&installation) {
+                Ok(()) => accepted.push(installation),
+                Err(reason) => rejected.push(RejectedJava {
+                    installation,
+                    reason,
+                }),
+            }
+        }
+
+        accepted.sort_by_cached_key(|i| {
+            (
+                settings.default.as_ref() != Some(&i.executable),
+                !requirement.is_preferred(i.major_version),
+                self.vendor_rank(i),
+                !is_64bit(i),
+                Reverse(version_key(&i.version)),
+            )
+        });
+
+        Ok((accepted, rejected))
+    }
+
+    pub async fn find_best(&self, requirement: &JavaRequirement) -> Result<JavaInstallation> {
+        let (accepted, rejected) = self.candidates(requirement).await?;
+
+        accepted.into_iter().next().ok_or(Error::NoSuitableJava {
+            requirement: *requirement,
+            rejected,
+        })
+    }
+
+    /// 为实例选择 Java：优先使用实例设置中固定的 Java，不检查版本要求
+    ///
+    /// 固定的 Java 无法使用时返回
+    /// [`Error::PinnedJavaUnavailable`]，不会改用其他 Java。
+    pub async fn find_for_instance(
+        &self,
+        instance: &Instance,
+        requirement: &JavaRequirement,
+    ) -> Result<JavaInstallation> {
+        let Some(executable) = &instance.settings.java_path else {
+            return self.find_best(requirement).await;
+        };
+
+        let installations = self.get_installations().await?;
+        match installations
             .into_iter()
-            .find(|i| i.satisfies(required))
-            .ok_or(Error::NoSuitableJava(required.major))
+            .find(|i| &i.executable == executable)
+        {
+            Some(installation) => Ok(installation),
+            None => JavaProbe::probe(executable)
+                .await
+                .map_err(|_| Error::PinnedJavaUnavailable(executable.clone())),
+        }
+    }
+
+    pub async fn settings(&self) -> JavaSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// 手动添加 Java，路径必须能通过探测
+    pub async fn add_custom(&self, executable: impl AsRef<Path>) -> Result<JavaInstallation> {
+        let mut installation = JavaProbe::probe(executable.as_ref()).await?;
+        installation.source = JavaSource::Custom;
+
+        self.update_settings(|settings| {
+            if !settings.custom.contains(&installation.executable) {
+                settings.custom.push(installation.executable.clone());
+            }
+        })
+        .await?;
+        self.insert_cached(&installation).await;
+
+        Ok(installation)
+    }
+
+    pub async fn remove_custom(&self, executable: &Path) -> Result<()> {
+        self.update_settings(|settings| settings.custom.retain(|p| p != executable))
+            .await?;
+
+        let mut cache = self.cache.write().await;
+        if let Some(installations) = cache.as_mut() {
+            installations.retain(|i| i.source != JavaSource::Custom || i.executable != executable);
+        }
+
+        Ok(())
+    }
+
+    /// 递归扫描用户选择的文件夹，找到的 Java 会作为手动添加的安装保存
+    pub async fn scan_folder(&self, dir: impl AsRef<Path>) -> Result<Vec<JavaInstallation>> {
+        let mut found = DirectoryScanner::scan_recursive(dir, FOLDER_SCAN_DEPTH).await?;
+        for installation in &mut found {
+            installation.source = JavaSource::Custom;
+        }
+
+        self.update_settings(|settings| {
+            for installation in &found {
+                if !settings.custom.contains(&installation.executable) {
+                    settings.custom.push(installation.executable.clone());
+                }
+            }
+        })
+        .await?;
+        for installation in &found {
+            self.insert_cached(installation).await;
+        }
+
+        Ok(found)
+    }
+
+    pub async fn set_hidden(&self, executable: &Path, hidden: bool) -> Result<()> {
+        self.update_settings(|settings| {
+            settings.hidden.retain(|p| p != executable);
+            if hidden {
+                settings.hidden.push(executable.to_path_buf());
+            }
+        })
+        .await
+    }
+
+    pub async fn set_default(&self, executable: Option<PathBuf>) -> Result<()> {
+        self.update_settings(|settings| settings.default = executable)
+            .await
+    }
+
+    async fn update_settings(&self, f: impl FnOnce(&mut JavaSettings)) -> Result<()> {
+        let mut settings = self.settings.write().await;
+
+        // 以磁盘上的配置为准，避免在首次刷新前修改时覆盖已有配置
+        if let Some(path) = &self.settings_file {
+            *settings = JavaSettings::load(path).await?;
+            f(&mut settings);
+            settings.save(path).await?;
+        } else {
+            f(&mut settings);
+        }
+
+        Ok(())
+    }
+
+    async fn insert_cached(&self, installation: &JavaInstallation) {
+        let mut cache = self.cache.write().await;
+        if let Some(installations) = cache.as_mut()
+            && !contains(installations, &installation.executable)
+        {
+            installations.push(installation.clone());
+        }
+    }
+
+    fn vendor_rank(&self, installation: &JavaInstallation) -> usize {
+        let vendor = installation
+            .vendor
+            .as_deref()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        self.preferred_vendors
+            .iter()
+            .position(|preferred| vendor.contains(preferred.as_str()))
+            .unwrap_or(self.preferred_vendors.len())
+    }
+
+    /// 下载并安装官方运行时组件，如 [`crate::components::GAMMA`]
+    pub async fn install_runtime(&self, component: &str) -> Result<JavaInstallation> {
+        let runtimes = self.runtimes.as_ref().ok_or(Error::UnsupportedPlatform)?;
+        let installation = runtimes.install(component).await?;
+        self.remember(&installation).await;
+
+        Ok(installation)
+    }
+
+    /// 下载并安装第三方发行版到运行时目录，之后的 [`Self::refresh`]
+    /// 同样会找到它
+    pub async fn install_distribution(
+        &self,
+        provider: &dyn JavaDistributionProvider,
+        major: u32,
+    ) -> Result<JavaInstallation> {
+        let runtimes = self.runtimes.as_ref().ok_or(Error::UnsupportedPlatform)?;
+        let installation = DistributionInstaller::new(runtimes.root())
+            .install(provider, major)
+            .await?;
+        self.remember(&installation).await;
+
+        Ok(installation)
+    }
+
+    /// 将新安装的 Java 加入已有的检测结果
+    async fn remember(&self, installation: &JavaInstallation) {
+        let mut cache = self.cache.write().await;
+        if let Some(installations) = cache.as_mut() {
+            installations.retain(|i| i.executable != installation.executable);
+            installations.push(installation.clone());
+        }
+    }
+
+    /// 查找合适的 Java，找不到时自动下载指定的官方运行时组件
+    pub async fn find_or_install(
+        &self,
+        requirement: &JavaRequirement,
+        component: &str,
+    ) -> Result<JavaInstallation> {
+        match self.find_best(requirement).await {
+            Err(Error::NoSuitableJava { .. }) if self.runtimes.is_some() => {
+                self.install_runtime(component).await
+            }
+            result => result,
+        }
     }
 
     pub async fn clear_cache(&self) {
@@ -56,3 +366,27 @@ impl Default for JavaManager {
         Self::new()
     }
 }
+
+/// 按规范路径判断是否已经包含同一个 Java
+fn contains(installations: &[JavaInstallation], executable: &Path) -> bool {
+    let canonical = std::fs::canonicalize(executable).unwrap_or_else(|_| executable.to_path_buf());
+    installations.iter().any(|i| {
+        i.executable == executable
+            || std::fs::canonicalize(&i.executable).is_ok_and(|path| path == canonical)
+    })
+}
+
+fn is_64bit(installation: &JavaInstallation) -> bool {
+    installation
+        .arch
+        .as_deref()
+        .is_some_and(|arch| arch.contains("64"))
+}
+
+/// `1.8.0_392` -> `[1, 8, 0, 392]`，用于比较同一主版本的补丁号
+fn version_key(version: &str) -> Vec<u32> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use unml_core::ModLoader;
+
+use crate::{JavaInstallation, JavaVersion};
+
+/// 游戏对 Java 版本的要求（闭区间）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JavaRequirement {
+    pub min: JavaVersion,
+    /// 为空表示不限制上限
+    pub max: Option<JavaVersion>,
+    /// 最理想的版本，通常为版本 JSON 中声明的版本
+    pub preferred: Option<JavaVersion>,
+}
+
+impl JavaRequirement {
+    pub fn at_least(min: JavaVersion) -> Self {
+        Self {
+            min,
+            max: None,
+            preferred: Some(min),
+        }
+    }
+
+    pub fn exactly(version: JavaVersion) -> Self {
+        Self {
+            min: version,
+            max: Some(version),
+            preferred: Some(version),
+        }
+    }
+
+    pub fn between(min: JavaVersion, max: JavaVersion) -> Self {
+        Self {
+            min,
+            max: Some(max),
+            preferred: Some(min),
+        }
+    }
+
+    /// 从版本 JSON 的 `javaVersion.majorVersion` 构造，缺失时（1.17
+    /// 之前的版本）视为 Java 8
+    pub fn from_major_version(major: Option<u32>) -> Self {
+        Self::at_least(major.map_or(JavaVersion::JAVA_8, JavaVersion::new))
+    }
+
+    /// 从完整的版本 JSON 中读取 `javaVersion.majorVersion`
+    pub fn from_version_json(json: &serde_json::Value) -> Self {
+        let major = json
+            .get("javaVersion")
+            .and_then(|java| java.get("majorVersion"))
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|major| u32::try_from(major).ok());
+
+        Self::from_major_version(major)
+    }
+
+    /// 叠加 Mod 加载器的限制
+    ///
+    /// 1.12.2 及更早的 Forge 依赖 Java 8 的类加载器实现，在更新的 Java
+    /// 上无法启动。
+    pub fn with_loader(self, loader: ModLoader, game_version: &str) -> Self {
+        match loader {
+            ModLoader::Forge if minor_version(game_version).is_some_and(|minor| minor <= 12) => {
+                Self::exactly(JavaVersion::JAVA_8)
+            }
+            _ => self,
+        }
+    }
+
+    pub fn contains(&self, major: u32) -> bool {
+        major >= self.min.major && self.max.is_none_or(|max| major <= max.major)
+    }
+
+    pub fn is_preferred(&self, major: u32) -> bool {
+        self.preferred
+            .is_some_and(|preferred| preferred.major == major)
+    }
+
+    /// 检查安装是否满足要求，不满足时返回原因
+    pub fn check(&self, installation: &JavaInstallation) -> Result<(), RejectReason> {
+        let found = installation.major_version;
+        if found < self.min.major {
+            return Err(RejectReason::TooOld {
+                found,
+                min: self.min.major,
+            });
+        }
+        if let Some(max) = self.max
+            && found > max.major
+        {
+            return Err(RejectReason::TooNew {
+                found,
+                max: max.major,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl From<JavaVersion> for JavaRequirement {
+    fn from(version: JavaVersion) -> Self {
+        Self::at_least(version)
+    }
+}
+
+impl fmt::Display for JavaRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "Java {}", self.min.major),
+            Some(max) => write!(f, "Java {}-{}", self.min.major, max.major),
+            None => write!(f, "Java {}+", self.min.major),
+        }
+    }
+}
+
+/// 候选 Java 被排除的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectReason {
+    TooOld {
+        found: u32,
+        min: u32,
+    },
+    TooNew {
+        found: u32,
+        max: u32,
+    },
+    /// 用户隐藏了该安装
+    Hidden,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooOld { found, min } => {
+                write!(f, "Java {found} is older than the required Java {min}")
+            }
+            Self::TooNew { found, max } => {
+                write!(f, "Java {found} is newer than the supported Java {max}")
+            }
+            Self::Hidden => write!(f, "hidden by the user"),
+        }
+    }
+}
+
+/// 被排除的候选 Java
+#[derive(Debug, Clone)]
+pub struct RejectedJava {
+    pub installation: JavaInstallation,
+    pub reason: RejectReason,
+}
+
+/// `1.12.2` -> `12`，快照等非正式版本返回 `None`
+fn minor_version(game_version: &str) -> Option<u32> {
+    let mut parts = game_version.split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+
+    parts.next()?.parse().ok()
+}
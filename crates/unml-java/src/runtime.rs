@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use futures::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use tokio::io::AsyncWriteExt;
+use unml_core::file_sha1;
+
+use crate::detector::probe::JavaProbe;
+use crate::distribution::{fetch_bytes, fetch_json, find_java_home};
+use crate::{Error, JavaInstallation, JavaSource, Result, http_client};
+
+/// Mojang Java 运行时清单
+const RUNTIME_INDEX_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+
+/// 安装完成标记文件，内容为运行时版本号
+///
+/// 第三方发行版安装时也会写入，使其同样被 [`RuntimeInstaller::installed`]
+/// 找到。
+pub(crate) const VERSION_MARKER: &str = ".version";
+
+const MAX_CONCURRENT_DOWNLOADS: usize = 8;
+
+/// 官方运行时组件名，对应版本 JSON 中的 `javaVersion.component`
+pub mod components {
+    /// Java 16
+    pub const ALPHA: &str = "java-runtime-alpha";
+    /// Java 17
+    pub const GAMMA: &str = "java-runtime-gamma";
+    /// Java 21
+    pub const DELTA: &str = "java-runtime-delta";
+    /// Java 8
+    pub const LEGACY: &str = "jre-legacy";
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeEntry {
+    manifest: RemoteFile,
+    version: RuntimeVersion,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeVersion {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RemoteFile {
+    sha1: String,
+    size: u64,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeManifest {
+    files: HashMap<String, RuntimeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RuntimeFile {
+    File {
+        #[serde(default)]
+        executable: bool,
+        downloads: RuntimeFileDownloads,
+    },
+    Directory,
+    Link {
+        target: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeFileDownloads {
+    raw: RemoteFile,
+}
+
+/// 从 Mojang 的 java-runtime 清单下载并管理官方 Java 运行时
+pub struct RuntimeInstaller {
+    root: PathBuf,
+}
+
+impl RuntimeInstaller {
+    /// `root` 为启动器管理的运行时目录，每个组件安装在其下的同名子目录中
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 当前平台在运行时清单中的名称
+    pub fn platform() -> Option<&'static str> {
+        match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("windows", "x86_64") => Some("windows-x64"),
+            ("windows", "x86") => Some("windows-x86"),
+            ("windows", "aarch64") => Some("windows-arm64"),
+            ("macos", "x86_64") => Some("mac-os"),
+            ("macos", "aarch64") => Some("mac-os-arm64"),
+            ("linux", "x86_64") => Some("linux"),
+            ("linux", "x86") => Some("linux-i386"),
+            _ => None,
+        }
+    }
+
+    pub fn component_dir(&self, component: &str) -> PathBuf {
+        self.root.join(component)
+    }
+
+    /// 组件中 Java 的主目录（macOS 上位于 bundle 内）
+    fn java_home(&self, component: &str) -> PathBuf {
+        let dir = self.component_dir(component);
+        if cfg!(target_os = "macos") {
+            dir.join("jre.bundle").join("Contents").join("Home")
+        } else {
+            dir
+        }
+    }
+
+    /// 列出当前平台可用的组件及其版本
+    pub async fn available(&self) -> Result<Vec<(String, String)>> {
+        let mut index = Self::fetch_index().await?;
+        let platform = Self::platform().ok_or(Error::UnsupportedPlatform)?;
+
+        let mut components: Vec<(String, String)> = index
+            .remove(platform)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(component, entries)| {
+                let entry = entries.into_iter().next()?;
+                Some((component, entry.version.name))
+            })
+            .collect();
+        components.sort();
+
+        Ok(components)
+    }
+
+    /// 列出已安装的运行时
+    pub async fn installed(&self) -> Result<Vec<JavaInstallation>> {
+        let mut installations = Vec::new();
+
+        if !tokio::fs::try_exists(&self.root).await? {
+            return Ok(installations);
+        }
+
+        let mut entries = tokio::fs::read_dir(&self.root).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let component = entry.file_name().to_string_lossy().into_owned();
+            if !tokio::fs::try_exists(entry.path().join(VERSION_MARKER)).await? {
+                continue;
+            }
+
+            if let Ok(installation) = self.probe(&component).await {
+                installations.push(installation);
+            }
+        }
+
+        Ok(installations)
+    }
+
+    /// 安装（或修复）指定组件
+    ///
+    /// 已存在且校验通过的文件不会重新下载。
+    pub async fn install(&self, component: &str) -> Result<JavaInstallation> {
+        let platform = Self::platform().ok_or(Error::UnsupportedPlatform)?;
+        let mut index = Self::fetch_index().await?;
+        let entry = index
+            .remove(platform)
+            .and_then(|mut components| components.remove(component))
+            .and_then(|entries| entries.into_iter().next())
+            .ok_or_else(|| Error::RuntimeUnavailable(component.to_owned()))?;
+
+        let manifest: RuntimeManifest = fetch_verified_json(&entry.manifest).await?;
+        let dir = self.component_dir(component);
+
+        // 先建目录，再下载文件，最后创建链接（链接目标需要已存在）
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+        for (path, file) in manifest.files {
+            let target = dir.join(&path);
+            match file {
+                RuntimeFile::Directory => tokio::fs::create_dir_all(&target).await?,
+                RuntimeFile::File {
+                    executable,
+                    downloads,
+                } => files.push((target, downloads.raw, executable)),
+                RuntimeFile::Link { target: link } => links.push((target, link)),
+            }
+        }
+
+        futures::stream::iter(files)
+            .map(|(path, remote, executable)| async move {
+                download_verified(&remote, &path).await?;
+                if executable {
+                    set_executable(&path).await?;
+                }
+                Ok::<_, Error>(())
+            })
+            .buffer_unordered(MAX_CONCURRENT_DOWNLOADS)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        for (path, target) in links {
+            create_link(&path, &target).await?;
+        }
+
+        tokio::fs::write(dir.join(VERSION_MARKER), &entry.version.name).await?;
+
+        self.probe(component).await
+    }
+
+    async fn probe(&self, component: &str) -> Result<JavaInstallation> {
+        let mut home = self.java_home(component);
+        // 第三方发行版保留压缩包的目录结构
+        if !home.join("bin").exists()
+            && let Some(found) = find_java_home(&self.component_dir(component))
+        {
+            home = found;
+        }
+        let executable = home.join("bin").join(JavaInstallation::executable_name());
+
+        let mut installation = JavaProbe::probe(&executable).await?;
+        installation.home = home;
+        installation.source = JavaSource::Managed;
+
+        Ok(installation)
+    }
+
+    async fn fetch_index() -> Result<HashMap<String, HashMap<String, Vec<RuntimeEntry>>>> {
+        fetch_json(RUNTIME_INDEX_URL).await
+    }
+}
+
+/// 下载 JSON 并校验 SHA-1
+async fn fetch_verified_json<T: serde::de::DeserializeOwned>(remote: &RemoteFile) -> Result<T> {
+    let body = fetch_bytes(&remote.url).await?;
+
+    let actual = format!("{:x}", Sha1::digest(&body));
+    if actual != remote.sha1 {
+        return Err(unml_core::ChecksumError {
+            expected: remote.sha1.clone(),
+            actual,
+        }
+        .into());
+    }
+
+    Ok(serde_json::from_slice(&body).map_err(unml_core::JsonError)?)
+}
+
+/// 下载文件并校验 SHA-1，已存在且校验通过时跳过
+async fn download_verified(remote: &RemoteFile, dest: &Path) -> Result<()> {
+    if let Ok(metadata) = tokio::fs::metadata(dest).await
+        && metadata.len() == remote.size
+        && file_sha1(dest).await.as_deref() == Some(remote.sha1.as_str())
+    {
+        return Ok(());
+    }
+
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let response = http_client()
+        .get(&remote.url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+    // 追加到完整文件名，避免 `java.exe` 与 `java.dll` 等同名文件并发下载时冲突
+    let mut tmp = dest.as_os_str().to_owned();
+    tmp.push(".part");
+    let tmp = PathBuf::from(tmp);
+    let mut file = tokio::fs::File::create(&tmp).await?;
+    let mut hasher = Sha1::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| unml_core::HttpError(e.to_string()))?;
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    drop(file);
+
+    let actual = format!("{:x}", hasher.finalize());
+    if actual != remote.sha1 {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(unml_core::ChecksumError {
+            expected: remote.sha1.clone(),
+            actual,
+        }
+        .into());
+    }
+
+    tokio::fs::rename(&tmp, dest).await?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn set_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).await?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn create_link(path: &Path, target: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    if tokio::fs::symlink_metadata(path).await.is_ok() {
+        tokio::fs::remove_file(path).await?;
+    }
+    tokio::fs::symlink(target, path).await?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn create_link(_path: &Path, _target: &str) -> Result<()> {
+    // Windows 清单中不包含链接
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// 用户对 Java 的配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JavaSettings {
+    /// 手动添加的 Java 可执行文件
+    pub custom: Vec<PathBuf>,
+    /// 隐藏的 Java 可执行文件，不参与自动选择
+    pub hidden: Vec<PathBuf>,
+    /// 全局默认 Java
+    pub default: Option<PathBuf>,
+}
+
+impl JavaSettings {
+    /// 从文件加载，文件不存在时返回默认配置
+    pub async fn load(path: &Path) -> Result<Self> {
+        match tokio::fs::read(path).await {
+            Ok(data) => Ok(serde_json::from_slice(&data).map_err(unml_core::JsonError)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let data = serde_json::to_vec_pretty(self).map_err(unml_core::JsonError)?;
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, path).await?;
+
+        Ok(())
+    }
+
+    pub fn is_hidden(&self, executable: &Path) -> bool {
+        self.hidden.iter().any(|hidden| hidden == executable)
+    }
+}
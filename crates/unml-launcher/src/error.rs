@@ -8,14 +8,80 @@ pub enum Error {
     #[error("Launch failed: {0}")]
     LaunchFailed(String),
 
+    #[error("{} JVM argument issues prevent the game from starting", .0.len())]
+    InvalidJvmArgs(Vec<crate::JvmArgIssue>),
+
+    #[error("Library not found: {0}")]
+    LibraryNotFound(String),
+
+    #[error("Failed to extract native libraries: {0}")]
+    NativesExtract(String),
+
     #[error("Game directory not found")]
     GameDirNotFound,
 
+    #[error("Instance not found: {0}")]
+    InstanceNotFound(String),
+
+    #[error("Invalid instance: {0}")]
+    InvalidInstance(String),
+
+    #[error("Snapshot not found: {0}")]
+    SnapshotNotFound(String),
+
+    #[error("Instance was not installed from a {0} modpack")]
+    PackMismatch(String),
+
+    #[error("{} modpack files failed to download", .0.len())]
+    UpdateFailed(Vec<unml_mods::FileFailure>),
+
+    #[error("Modpack does not specify a Minecraft version")]
+    MissingGameVersion,
+
+    #[error("No dedicated server is available for {0}")]
+    ServerUnavailable(String),
+
+    #[error("Installing the client mod loader {0} is not supported yet")]
+    LoaderInstallUnsupported(String),
+
+    #[error("Unsupported server loader: {0}")]
+    UnsupportedLoader(String),
+
+    #[error("Server installer failed: {0}")]
+    ServerInstallFailed(String),
+
+    #[error("Server is not installed")]
+    ServerNotInstalled,
+
+    #[error("The Minecraft EULA has not been accepted")]
+    EulaNotAccepted,
+
+    #[error("Quick Play into a world or Realm is not supported by {0}")]
+    QuickPlayUnsupported(String),
+
+    #[error("Invalid NBT: {0}")]
+    InvalidNbt(String),
+
+    #[error("Server list ping failed: {0}")]
+    Ping(String),
+
+    #[error("authlib-injector is required for Yggdrasil accounts but was not configured")]
+    AuthlibInjectorMissing,
+
     #[error(transparent)]
     Io(#[from] unml_core::IoError),
 
     #[error(transparent)]
     Json(#[from] unml_core::JsonError),
+
+    #[error(transparent)]
+    Download(#[from] unml_download::Error),
+
+    #[error(transparent)]
+    Mods(#[from] unml_mods::Error),
+
+    #[error(transparent)]
+    Java(#[from] unml_java::Error),
 }
 
 impl From<std::io::Error> for Error {
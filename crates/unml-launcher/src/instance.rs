@@ -0,0 +1,314 @@
+mod curseforge;
+mod export;
+mod mrpack;
+mod multimc;
+mod server_pack;
+mod snapshot;
+mod update;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub use export::{DEFAULT_EXPORT_INCLUDE, ExportOptions, ExportReport};
+pub use server_pack::{
+    ClientOnlyMod, ClientOnlySource, DEFAULT_SERVER_PACK_INCLUDE, ServerPackOptions,
+    ServerPackReport,
+};
+use unml_core::{Instance, LoaderSpec, file_sha1};
+use unml_mods::{FileFailure, ManualDownload};
+pub use update::{ConflictKind, UpdateConflict, UpdateReport};
+use zip::write::SimpleFileOptions;
+
+use crate::multiplayer::{SERVERS_FILE, ServerList};
+use crate::world::{SAVES_DIR, WorldInfo, scan_worlds};
+use crate::{Error, Result};
+
+const INSTANCE_FILE: &str = "instance.json";
+/// 整合包安装的文件及其 SHA-1，用于更新时区分用户修改
+const PACK_FILES: &str = "pack-files.json";
+/// 实例目录中的游戏目录名，与 MultiMC/Prism 保持一致
+const GAME_DIR: &str = ".minecraft";
+
+/// 导入整合包的结果
+#[derive(Debug)]
+pub struct ImportReport {
+    pub instance: Instance,
+    /// 下载或校验失败的文件
+    pub failed: Vec<FileFailure>,
+    /// 需要用户手动下载的文件
+    pub manual: Vec<ManualDownload>,
+    /// 不影响导入但需要用户留意的问题
+    pub warnings: Vec<String>,
+}
+
+/// 管理实例目录，每个实例位于 `<root>/<id>/`
+pub struct InstanceManager {
+    root: PathBuf,
+}
+
+impl InstanceManager {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn instance_dir(&self, id: &str) -> PathBuf {
+        self.root.join(id)
+    }
+
+    pub fn game_dir(&self, id: &str) -> PathBuf {
+        self.instance_dir(id).join(GAME_DIR)
+    }
+
+    pub async fn list(&self) -> Result<Vec<Instance>> {
+        let mut instances = Vec::new();
+        if !tokio::fs::try_exists(&self.root).await? {
+            return Ok(instances);
+        }
+
+        let mut entries = tokio::fs::read_dir(&self.root).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let id = entry.file_name().to_string_lossy().into_owned();
+            if let Ok(instance) = self.load(&id).await {
+                instances.push(instance);
+            }
+        }
+        instances.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(instances)
+    }
+
+    pub async fn load(&self, id: &str) -> Result<Instance> {
+        let path = self.instance_dir(id).join(INSTANCE_FILE);
+        let data = match tokio::fs::read(&path).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::InstanceNotFound(id.to_owned()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(serde_json::from_slice(&data).map_err(unml_core::JsonError)?)
+    }
+
+    pub async fn save(&self, instance: &Instance) -> Result<()> {
+        let dir = self.instance_dir(&instance.id);
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let data = serde_json::to_vec_pretty(instance).map_err(unml_core::JsonError)?;
+        let tmp = dir.join(format!("{INSTANCE_FILE}.tmp"));
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, dir.join(INSTANCE_FILE)).await?;
+
+        Ok(())
+    }
+
+    /// 创建实例，ID 由名称生成并保证不与已有实例重复
+    pub async fn create(
+        &self,
+        name: &str,
+        game_version: &str,
+        loader: Option<LoaderSpec>,
+    ) -> Result<Instance> {
+        let id = self.unique_id(name).await?;
+
+        let mut instance = Instance::new(id, name, game_version);
+        instance.loader = loader;
+
+        tokio::fs::create_dir_all(self.game_dir(&instance.id)).await?;
+        self.save(&instance).await?;
+
+        Ok(instance)
+    }
+
+    /// 删除导入失败的实例，清理失败也不应掩盖原来的错误
+    async fn discard(&self, id: &str) {
+        let _ = tokio::fs::remove_dir_all(self.instance_dir(id)).await;
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let dir = self.instance_dir(id);
+        if !tokio::fs::try_exists(dir.join(INSTANCE_FILE)).await? {
+            return Err(Error::InstanceNotFound(id.to_owned()));
+        }
+        tokio::fs::remove_dir_all(dir).await?;
+
+        Ok(())
+    }
+
+    /// 实例的多人游戏服务器列表
+    pub async fn servers(&self, id: &str) -> Result<ServerList> {
+        if !tokio::fs::try_exists(self.instance_dir(id).join(INSTANCE_FILE)).await? {
+            return Err(Error::InstanceNotFound(id.to_owned()));
+        }
+        ServerList::load(&self.game_dir(id).join(SERVERS_FILE)).await
+    }
+
+    pub async fn save_servers(&self, id: &str, servers: &ServerList) -> Result<()> {
+        if !tokio::fs::try_exists(self.instance_dir(id).join(INSTANCE_FILE)).await? {
+            return Err(Error::InstanceNotFound(id.to_owned()));
+        }
+        servers.save(&self.game_dir(id).join(SERVERS_FILE)).await
+    }
+
+    /// 实例的单人游戏存档，按最后游玩时间倒序排列
+    pub async fn worlds(&self, id: &str) -> Result<Vec<WorldInfo>> {
+        if !tokio::fs::try_exists(self.instance_dir(id).join(INSTANCE_FILE)).await? {
+            return Err(Error::InstanceNotFound(id.to_owned()));
+        }
+        scan_worlds(&self.game_dir(id).join(SAVES_DIR)).await
+    }
+
+    /// 记录整合包安装的文件，`paths` 为相对于游戏目录的路径
+    async fn record_pack_files(&self, id: &str, paths: &[String]) -> Result<()> {
+        let game_dir = self.game_dir(id);
+
+        let mut files = HashMap::new();
+        for path in paths {
+            if let Some(sha1) = file_sha1(&game_dir.join(path)).await {
+                files.insert(path.clone(), sha1);
+            }
+        }
+
+        self.save_pack_files(id, &files).await
+    }
+
+    async fn load_pack_files(&self, id: &str) -> Result<HashMap<String, String>> {
+        match tokio::fs::read(self.instance_dir(id).join(PACK_FILES)).await {
+            Ok(data) => Ok(serde_json::from_slice(&data).map_err(unml_core::JsonError)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save_pack_files(&self, id: &str, files: &HashMap<String, String>) -> Result<()> {
+        let data = serde_json::to_vec_pretty(files).map_err(unml_core::JsonError)?;
+        tokio::fs::write(self.instance_dir(id).join(PACK_FILES), data).await?;
+
+        Ok(())
+    }
+
+    async fn unique_id(&self, name: &str) -> Result<String> {
+        let base: String = name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>()
+            .trim_matches(['-', '.'])
+            .to_owned();
+        let base = if base.is_empty() {
+            "instance".to_owned()
+        } else {
+            base
+        };
+
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while tokio::fs::try_exists(self.instance_dir(&id)).await? {
+            id = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+
+        Ok(id)
+    }
+}
+
+/// 递归复制目录，已存在的文件会被覆盖
+async fn copy_dir(src: &Path, dest: &Path) -> Result<()> {
+    let mut stack = vec![(src.to_path_buf(), dest.to_path_buf())];
+
+    while let Some((src, dest)) = stack.pop() {
+        tokio::fs::create_dir_all(&dest).await?;
+
+        let mut entries = tokio::fs::read_dir(&src).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let target = dest.join(entry.file_name());
+            if entry.file_type().await?.is_dir() {
+                stack.push((entry.path(), target));
+            } else {
+                tokio::fs::copy(entry.path(), target).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 展开 `start` 中的文件和目录，返回按路径排序的 (相对于 `root` 的路径,
+/// 绝对路径)
+async fn walk_files(root: &Path, mut start: Vec<PathBuf>) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+
+    while let Some(path) = start.pop() {
+        let Ok(metadata) = tokio::fs::metadata(&path).await else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            let mut entries = tokio::fs::read_dir(&path).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                start.push(entry.path());
+            }
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            files.push((relative, path));
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+/// 压缩包中的一项
+enum ZipEntry {
+    Data(Vec<u8>),
+    File(PathBuf),
+}
+
+/// 写入压缩包，先写临时文件再替换，避免留下不完整的文件
+async fn write_zip(dest: &Path, entries: Vec<(String, ZipEntry)>) -> Result<()> {
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp = dest.clone().into_os_string();
+        tmp.push(".part");
+        let tmp = PathBuf::from(tmp);
+
+        let mut zip = zip::ZipWriter::new(File::create(&tmp)?);
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+        for (name, entry) in entries {
+            zip.start_file(name, options)
+                .map_err(std::io::Error::from)?;
+            match entry {
+                ZipEntry::Data(data) => zip.write_all(&data)?,
+                ZipEntry::File(path) => {
+                    std::io::copy(&mut File::open(path)?, &mut zip)?;
+                }
+            }
+        }
+        zip.finish().map_err(std::io::Error::from)?;
+
+        std::fs::rename(&tmp, &dest)?;
+
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::InvalidInstance(e.to_string()))?
+}
@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use unml_core::{PackFormat, PackInfo, ProgressCallback};
+use unml_mods::{CurseForgePack, CurseForgePlatform};
+
+use super::{ImportReport, InstanceManager};
+use crate::{Error, Result};
+
+impl InstanceManager {
+    /// 从本地 CurseForge 整合包 zip 创建实例
+    ///
+    /// 禁止第三方下载的文件列在 [`ImportReport::manual`] 中，需要用户手动下载。
+    /// 启动器尚不能安装客户端加载器，需要加载器的整合包会以
+    /// [`Error::LoaderInstallUnsupported`] 失败，不会创建实例。
+    pub async fn import_curseforge(
+        &self,
+        path: impl AsRef<Path>,
+        platform: &CurseForgePlatform,
+        progress: Option<ProgressCallback>,
+    ) -> Result<ImportReport> {
+        let pack = CurseForgePack::open(path.as_ref()).await?;
+        let manifest = pack.manifest();
+        // 没有加载器的实例无法加载整合包中的 Mod，不创建无法使用的实例
+        if let Some(loader) = manifest.loader() {
+            return Err(Error::LoaderInstallUnsupported(format!(
+                "{:?} {}",
+                loader.loader, loader.version
+            )));
+        }
+
+        let mut instance = self
+            .create(&manifest.name, &manifest.minecraft.version, None)
+            .await?;
+
+        let result = async {
+            instance.pack = Some(PackInfo {
+                format: PackFormat::CurseForge,
+                name: manifest.name.clone(),
+                version: manifest.version.clone(),
+            });
+            self.save(&instance).await?;
+
+            let game_dir = self.game_dir(&instance.id);
+            let install = pack.install_files(platform, &game_dir, progress).await?;
+            let mut owned = pack.extract_overrides(&game_dir).await?;
+            owned.extend(install.installed);
+            self.record_pack_files(&instance.id, &owned).await?;
+
+            Ok((install.failed, install.manual))
+        }
+        .await;
+        let (failed, manual) = match result {
+            Ok(files) => files,
+            Err(e) => {
+                self.discard(&instance.id).await;
+                return Err(e);
+            }
+        };
+
+        let warnings: Vec<String> = manifest
+            .skipped_loaders()
+            .into_iter()
+            .map(|id| format!("unsupported mod loader skipped: {id}"))
+            .collect();
+
+        Ok(ImportReport {
+            instance,
+            failed,
+            manual,
+            warnings,
+        })
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use sha1::{Digest, Sha1};
+use unml_core::{Instance, ModLoader};
+use unml_mods::{
+    CurseForgeManifest, CurseForgeManifestFile, CurseForgeMinecraft, CurseForgeModLoader,
+    CurseForgePlatform, ModrinthPlatform, MrpackFile, MrpackHashes, MrpackIndex, fingerprint,
+    safe_join,
+};
+
+use super::{INSTANCE_FILE, InstanceManager, ZipEntry, walk_files, write_zip};
+use crate::Result;
+
+/// 默认导出的文件和目录（相对于游戏目录）
+pub const DEFAULT_EXPORT_INCLUDE: &[&str] = &[
+    "mods",
+    "config",
+    "resourcepacks",
+    "shaderpacks",
+    "options.txt",
+];
+
+/// 可以通过平台识别的内容所在的目录
+const CONTENT_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+/// 导出选项
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// 整合包版本号
+    pub version: String,
+    /// 要包含的文件或目录（相对于游戏目录），如
+    /// `config`、`saves`、`options.txt`
+    pub include: Vec<String>,
+}
+
+impl ExportOptions {
+    pub fn new() -> Self {
+        Self {
+            version: "1.0.0".to_owned(),
+            include: DEFAULT_EXPORT_INCLUDE
+                .iter()
+                .map(|path| (*path).to_owned())
+                .collect(),
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn with_include(mut self, include: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.include = include.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 导出的结果
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    /// 通过平台引用、不包含在压缩包中的文件
+    pub referenced: Vec<String>,
+    /// 未能识别而打包进 overrides 的 Mod、资源包与光影包
+    pub bundled: Vec<String>,
+}
+
+impl InstanceManager {
+    /// 导出为 `.mrpack`，能在 Modrinth 上识别的文件以下载地址引用
+    pub async fn export_mrpack(
+        &self,
+        id: &str,
+        dest: &Path,
+        options: &ExportOptions,
+        platform: &ModrinthPlatform,
+    ) -> Result<ExportReport> {
+        let instance = self.load(id).await?;
+        let files = self.collect_files(id, &options.include).await?;
+
+        let mut hashes = HashMap::new();
+        for (relative, path) in files.iter().filter(|(r, _)| is_content(r)) {
+            let data = tokio::fs::read(path).await?;
+            hashes.insert(relative.clone(), format!("{:x}", Sha1::digest(&data)));
+        }
+        let sha1s: Vec<String> = hashes.values().cloned().collect();
+        let versions = platform.get_versions_by_sha1(&sha1s).await?;
+
+        let mut report = ExportReport::default();
+        let mut index_files = Vec::new();
+        let mut entries = Vec::new();
+        for (relative, path) in files {
+            let remote = hashes.get(&relative).and_then(|sha1| {
+                versions
+                    .get(sha1)
+                    .and_then(|version| version.file_by_sha1(sha1))
+            });
+
+            match remote {
+                Some(remote) => {
+                    index_files.push(MrpackFile {
+                        path: relative.clone(),
+                        hashes: MrpackHashes {
+                            sha1: remote.hashes.sha1.clone(),
+                            sha512: remote.hashes.sha512.clone(),
+                        },
+                        env: None,
+                        downloads: vec![remote.url.clone()],
+                        file_size: remote.size,
+                    });
+                    report.referenced.push(relative);
+                }
+                None => {
+                    if hashes.contains_key(&relative) {
+                        report.bundled.push(relative.clone());
+                    }
+                    entries.push((format!("overrides/{relative}"), ZipEntry::File(path)));
+                }
+            }
+        }
+
+        let mut dependencies = HashMap::from([("minecraft".to_owned(), instance.game_version)]);
+        if let Some(loader) = instance.loader {
+            dependencies.insert(mrpack_loader_key(loader.loader).to_owned(), loader.version);
+        }
+        let index = MrpackIndex {
+            format_version: 1,
+            game: "minecraft".to_owned(),
+            version_id: options.version.clone(),
+            name: instance.name,
+            summary: None,
+            files: index_files,
+            dependencies,
+        };
+        let index = serde_json::to_vec_pretty(&index).map_err(unml_core::JsonError)?;
+        entries.push(("modrinth.index.json".to_owned(), ZipEntry::Data(index)));
+
+        write_zip(dest, entries).await?;
+
+        Ok(report)
+    }
+
+    /// 导出为 CurseForge 整合包，能通过指纹识别的文件写入 `manifest.json`
+    pub async fn export_curseforge(
+        &self,
+        id: &str,
+        dest: &Path,
+        options: &ExportOptions,
+        platform: &CurseForgePlatform,
+    ) -> Result<ExportReport> {
+        let instance = self.load(id).await?;
+        let files = self.collect_files(id, &options.include).await?;
+
+        let mut fingerprints = HashMap::new();
+        for (relative, path) in files.iter().filter(|(r, _)| is_content(r)) {
+            let data = tokio::fs::read(path).await?;
+            fingerprints.insert(relative.clone(), fingerprint(&data));
+        }
+        let values: Vec<u32> = fingerprints.values().copied().collect();
+        let matched: HashMap<u32, _> = platform
+            .match_fingerprints(&values)
+            .await?
+            .into_iter()
+            .map(|file| (file.file_fingerprint, file))
+            .collect();
+
+        let mut report = ExportReport::default();
+        let mut manifest_files = Vec::new();
+        let mut entries = Vec::new();
+        for (relative, path) in files {
+            let remote = fingerprints
+                .get(&relative)
+                .and_then(|fingerprint| matched.get(fingerprint));
+
+            match remote {
+                Some(remote) => {
+                    manifest_files.push(CurseForgeManifestFile {
+                        project_id: remote.mod_id,
+                        file_id: remote.id,
+                        required: true,
+                    });
+                    report.referenced.push(relative);
+                }
+                None => {
+                    if fingerprints.contains_key(&relative) {
+                        report.bundled.push(relative.clone());
+                    }
+                    entries.push((format!("overrides/{relative}"), ZipEntry::File(path)));
+                }
+            }
+        }
+
+        let mod_loaders = instance
+            .loader
+            .iter()
+            .map(|loader| CurseForgeModLoader {
+                id: format!(
+                    "{}-{}",
+                    curseforge_loader_prefix(loader.loader),
+                    loader.version
+                ),
+                primary: true,
+            })
+            .collect();
+        let manifest = CurseForgeManifest {
+            minecraft: CurseForgeMinecraft {
+                version: instance.game_version,
+                mod_loaders,
+            },
+            manifest_type: "minecraftModpack".to_owned(),
+            manifest_version: 1,
+            name: instance.name,
+            version: options.version.clone(),
+            author: String::new(),
+            files: manifest_files,
+            overrides: "overrides".to_owned(),
+        };
+        let manifest = serde_json::to_vec_pretty(&manifest).map_err(unml_core::JsonError)?;
+        entries.push(("manifest.json".to_owned(), ZipEntry::Data(manifest)));
+
+        write_zip(dest, entries).await?;
+
+        Ok(report)
+    }
+
+    /// 导出为完整压缩包：实例配置与选中的游戏文件原样打包
+    pub async fn export_archive(
+        &self,
+        id: &str,
+        dest: &Path,
+        options: &ExportOptions,
+    ) -> Result<ExportReport> {
+        let instance: Instance = self.load(id).await?;
+        let files = self.collect_files(id, &options.include).await?;
+
+        let instance = serde_json::to_vec_pretty(&instance).map_err(unml_core::JsonError)?;
+        let mut entries = vec![(INSTANCE_FILE.to_owned(), ZipEntry::Data(instance))];
+        entries.extend(
+            files
+                .into_iter()
+                .map(|(relative, path)| (format!(".minecraft/{relative}"), ZipEntry::File(path))),
+        );
+
+        write_zip(dest, entries).await?;
+
+        Ok(ExportReport::default())
+    }
+
+    /// 展开选中的文件和目录，返回按路径排序的 (相对路径, 绝对路径)
+    async fn collect_files(&self, id: &str, include: &[String]) -> Result<Vec<(String, PathBuf)>> {
+        let game_dir = self.game_dir(id);
+        let start = include
+            .iter()
+            .filter_map(|path| safe_join(&game_dir, path))
+            .collect();
+
+        walk_files(&game_dir, start).await
+    }
+}
+
+/// 直接位于 Mod、资源包或光影包目录中的 jar/zip
+fn is_content(relative: &str) -> bool {
+    let Some((dir, name)) = relative.split_once('/') else {
+        return false;
+    };
+
+    CONTENT_DIRS.contains(&dir)
+        && !name.contains('/')
+        && (name.ends_with(".jar") || name.ends_with(".zip"))
+}
+
+fn mrpack_loader_key(loader: ModLoader) -> &'static str {
+    match loader {
+        ModLoader::Forge => "forge",
+        ModLoader::NeoForge => "neoforge",
+        ModLoader::Fabric => "fabric-loader",
+        ModLoader::Quilt => "quilt-loader",
+    }
+}
+
+fn curseforge_loader_prefix(loader: ModLoader) -> &'static str {
+    match loader {
+        ModLoader::Forge => "forge",
+        ModLoader::NeoForge => "neoforge",
+        ModLoader::Fabric => "fabric",
+        ModLoader::Quilt => "quilt",
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use unml_core::{PackFormat, PackInfo, ProgressCallback};
+use unml_mods::{Mrpack, PackSide};
+
+use super::{ImportReport, InstanceManager};
+use crate::{Error, Result};
+
+impl InstanceManager {
+    /// 从本地 `.mrpack` 文件创建实例
+    ///
+    /// 除了索引中列出的文件地址外不访问 Modrinth API。`progress`
+    /// 报告已下载的文件数与总数。
+    pub async fn import_mrpack(
+        &self,
+        path: impl AsRef<Path>,
+        progress: Option<ProgressCallback>,
+    ) -> Result<ImportReport> {
+        let pack = Mrpack::open(path.as_ref()).await?;
+        let index = pack.index();
+
+        let game_version = index.game_version().ok_or(Error::MissingGameVersion)?;
+        let mut instance = self
+            .create(&index.name, game_version, index.loader())
+            .await?;
+
+        let result = async {
+            instance.pack = Some(PackInfo {
+                format: PackFormat::Modrinth,
+                name: index.name.clone(),
+                version: index.version_id.clone(),
+            });
+            self.save(&instance).await?;
+
+            let game_dir = self.game_dir(&instance.id);
+            let failed = pack
+                .download_files(&game_dir, PackSide::Client, progress)
+                .await;
+            let mut owned = pack.extract_overrides(&game_dir, PackSide::Client).await?;
+            owned.extend(
+                index
+                    .files_for(PackSide::Client)
+                    .filter(|file| !failed.iter().any(|f| f.path == file.path))
+                    .map(|file| file.path.clone()),
+            );
+            self.record_pack_files(&instance.id, &owned).await?;
+
+            Ok(failed)
+        }
+        .await;
+
+        match result {
+            Ok(failed) => Ok(ImportReport {
+                instance,
+                failed,
+                manual: Vec::new(),
+                warnings: Vec::new(),
+            }),
+            Err(e) => {
+                self.discard(&instance.id).await;
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Deserialize;
+use unml_core::{InstanceSettings, LoaderSpec, ModLoader};
+
+use super::{ImportReport, InstanceManager, copy_dir};
+use crate::{Error, Result};
+
+const INSTANCE_CFG: &str = "instance.cfg";
+const PACK_FILE: &str = "mmc-pack.json";
+
+#[derive(Debug, Deserialize)]
+struct MmcPack {
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MmcComponent {
+    uid: String,
+    version: Option<String>,
+    #[serde(rename = "cachedName")]
+    cached_name: Option<String>,
+}
+
+/// 不影响导入结果的组件（依赖库或由加载器自动补全）
+const IGNORED_COMPONENTS: &[&str] = &[
+    "org.lwjgl",
+    "org.lwjgl3",
+    "net.fabricmc.intermediary",
+    "org.quiltmc.hashed",
+];
+
+impl InstanceManager {
+    /// 从 MultiMC/Prism 实例目录或导出的 zip 创建实例
+    ///
+    /// 无法识别的组件（如 jar mod）不会导致导入失败，而是记录在
+    /// [`ImportReport::warnings`] 中。
+    pub async fn import_multimc(&self, path: impl AsRef<Path>) -> Result<ImportReport> {
+        let path = path.as_ref();
+
+        if tokio::fs::metadata(path).await?.is_dir() {
+            return self.import_multimc_dir(path).await;
+        }
+
+        // 压缩包先解压到实例根目录下的临时目录，每次导入使用不同的目录
+        static NEXT_STAGING: AtomicU64 = AtomicU64::new(0);
+        let staging = self.root().join(format!(
+            ".import-{}-{}",
+            std::process::id(),
+            NEXT_STAGING.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let result = async {
+            extract_zip(path, &staging).await?;
+            match find_instance_root(&staging).await {
+                Some(dir) => self.import_multimc_dir(&dir).await,
+                None => Err(Error::InvalidInstance(format!("{INSTANCE_CFG} not found"))),
+            }
+        }
+        .await;
+
+        // 解压失败时可能还没有创建临时目录，清理失败也不应掩盖原来的错误
+        match tokio::fs::remove_dir_all(&staging).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound && result.is_ok() => Err(e.into()),
+            _ => result,
+        }
+    }
+
+    async fn import_multimc_dir(&self, dir: &Path) -> Result<ImportReport> {
+        let cfg = match tokio::fs::read_to_string(dir.join(INSTANCE_CFG)).await {
+            Ok(cfg) => parse_cfg(&cfg),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::InvalidInstance(format!("{INSTANCE_CFG} not found")));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let pack = tokio::fs::read(dir.join(PACK_FILE))
+            .await
+            .map_err(|_| Error::InvalidInstance(format!("{PACK_FILE} not found")))?;
+        let pack: MmcPack = serde_json::from_slice(&pack).map_err(unml_core::JsonError)?;
+
+        let mut warnings = Vec::new();
+        let mut game_version = None;
+        let mut loader = None;
+        for component in pack.components {
+            let version = component.version.clone().unwrap_or_default();
+            let mapped = match component.uid.as_str() {
+                "net.minecraft" => {
+                    game_version = Some(version);
+                    continue;
+                }
+                "net.minecraftforge" => ModLoader::Forge,
+                "net.neoforged" => ModLoader::NeoForge,
+                "net.fabricmc.fabric-loader" => ModLoader::Fabric,
+                "org.quiltmc.quilt-loader" => ModLoader::Quilt,
+                uid if IGNORED_COMPONENTS.contains(&uid) => continue,
+                uid => {
+                    let name = component.cached_name.as_deref().unwrap_or(uid);
+                    warnings.push(format!("unsupported component skipped: {name}"));
+                    continue;
+                }
+            };
+
+            if loader.is_some() {
+                warnings.push(format!("extra loader skipped: {}", component.uid));
+            } else {
+                loader = Some(LoaderSpec {
+                    loader: mapped,
+                    version,
+                });
+            }
+        }
+        let game_version = game_version.ok_or(Error::MissingGameVersion)?;
+
+        let name = cfg
+            .get("name")
+            .cloned()
+            .or_else(|| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        let mut instance = self.create(&name, &game_version, loader).await?;
+
+        let result = async {
+            instance.settings = settings_from_cfg(&cfg);
+            self.save(&instance).await?;
+
+            // 较旧的 MultiMC 使用 minecraft/ 而不是 .minecraft/
+            for game_dir in [".minecraft", "minecraft"] {
+                let source = dir.join(game_dir);
+                if tokio::fs::try_exists(&source).await? {
+                    copy_dir(&source, &self.game_dir(&instance.id)).await?;
+                    break;
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            self.discard(&instance.id).await;
+            return Err(e);
+        }
+
+        Ok(ImportReport {
+            instance,
+            failed: Vec::new(),
+            manual: Vec::new(),
+            warnings,
+        })
+    }
+}
+
+/// 解析 `instance.cfg`（INI 格式，只有一个分组）
+fn parse_cfg(cfg: &str) -> HashMap<String, String> {
+    cfg.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_owned(), value.to_owned())
+        })
+        .collect()
+}
+
+/// 只有打开了对应的覆盖开关时才沿用实例自己的设置
+fn settings_from_cfg(cfg: &HashMap<String, String>) -> InstanceSettings {
+    let enabled = |key: &str| cfg.get(key).is_some_and(|v| v == "true");
+    let value = |key: &str| cfg.get(key).filter(|v| !v.is_empty());
+
+    let mut settings = InstanceSettings::default();
+    if enabled("OverrideJavaLocation") {
+        settings.java_path = value("JavaPath").map(PathBuf::from);
+    }
+    if enabled("OverrideMemory") {
+        settings.min_memory_mb = value("MinMemAlloc").and_then(|v| v.parse().ok());
+        settings.max_memory_mb = value("MaxMemAlloc").and_then(|v| v.parse().ok());
+    }
+    if enabled("OverrideJavaArgs") {
+        settings.jvm_args = value("JvmArgs")
+            .map(|args| args.split_whitespace().map(str::to_owned).collect())
+            .unwrap_or_default();
+    }
+
+    settings
+}
+
+async fn extract_zip(archive: &Path, dest: &Path) -> Result<()> {
+    let (archive, dest) = (archive.to_path_buf(), dest.to_path_buf());
+
+    tokio::task::spawn_blocking(move || {
+        zip::ZipArchive::new(File::open(&archive)?)
+            .and_then(|mut zip| zip.extract(&dest))
+            .map_err(|e| Error::InvalidInstance(e.to_string()))
+    })
+    .await
+    .map_err(|e| Error::InvalidInstance(e.to_string()))?
+}
+
+/// 导出的 zip 中 `instance.cfg` 可能位于根目录或唯一的子目录中
+async fn find_instance_root(dir: &Path) -> Option<PathBuf> {
+    if tokio::fs::try_exists(dir.join(INSTANCE_CFG)).await.ok()? {
+        return Some(dir.to_path_buf());
+    }
+
+    let mut entries = tokio::fs::read_dir(dir).await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if tokio::fs::try_exists(path.join(INSTANCE_CFG))
+            .await
+            .unwrap_or(false)
+        {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("unml-multimc-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    /// 实例根目录中除导入的实例外没有残留的临时目录
+    fn leftovers(root: &Path) -> Vec<String> {
+        std::fs::read_dir(root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with(".import-"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn zip_imports_clean_up_staging() {
+        let root = temp_root("zip");
+        let manager = InstanceManager::new(root.join("instances"));
+        std::fs::create_dir_all(manager.root()).unwrap();
+
+        let packs = ["First", "Second"].map(|name| {
+            let path = root.join(format!("{name}.zip"));
+            write_zip(
+                &path,
+                &[
+                    (&format!("{name}/instance.cfg"), &format!("name={name}\n")),
+                    (
+                        &format!("{name}/mmc-pack.json"),
+                        r#"{"components": [{"uid": "net.minecraft", "version": "1.20.1"}]}"#,
+                    ),
+                    (&format!("{name}/.minecraft/options.txt"), name),
+                ],
+            );
+            path
+        });
+        let missing_cfg = root.join("missing.zip");
+        write_zip(&missing_cfg, &[("readme.txt", "")]);
+        let corrupt = root.join("corrupt.zip");
+        std::fs::write(&corrupt, "not a zip").unwrap();
+
+        // 并发导入使用各自的临时目录
+        let (first, second) = tokio::join!(
+            manager.import_multimc(&packs[0]),
+            manager.import_multimc(&packs[1])
+        );
+        for (report, name) in [(first, "First"), (second, "Second")] {
+            let report = report.unwrap();
+            assert_eq!(report.instance.name, name);
+            let options = manager.game_dir(&report.instance.id).join("options.txt");
+            assert_eq!(std::fs::read_to_string(options).unwrap(), name);
+        }
+
+        assert!(matches!(
+            manager.import_multimc(&missing_cfg).await,
+            Err(Error::InvalidInstance(_))
+        ));
+        assert!(matches!(
+            manager.import_multimc(&corrupt).await,
+            Err(Error::InvalidInstance(_))
+        ));
+        assert_eq!(leftovers(manager.root()), Vec::<String>::new());
+        assert_eq!(manager.list().await.unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,372 @@
+use std::path::Path;
+
+use unml_core::{DownloadProvider, InstanceSettings, file_sha1};
+use unml_mods::{EnvSupport, ModrinthPlatform, PackSide, mod_side, safe_join};
+
+use super::{InstanceManager, ZipEntry, copy_dir, walk_files, write_zip};
+use crate::{DedicatedServer, Result, ServerInstall};
+
+/// 默认复制到服务端的目录（相对于游戏目录）
+pub const DEFAULT_SERVER_PACK_INCLUDE: &[&str] = &["config", "defaultconfigs", "kubejs", "scripts"];
+
+/// 生成服务端包时的临时目录（位于实例目录中）
+const SERVER_PACK_DIR: &str = ".server-pack";
+
+/// 服务端包选项
+#[derive(Debug, Clone)]
+pub struct ServerPackOptions {
+    /// 除 Mod 外要复制的文件或目录（相对于游戏目录）
+    pub include: Vec<String>,
+}
+
+impl ServerPackOptions {
+    pub fn new() -> Self {
+        Self {
+            include: DEFAULT_SERVER_PACK_INCLUDE
+                .iter()
+                .map(|path| (*path).to_owned())
+                .collect(),
+        }
+    }
+
+    pub fn with_include(mut self, include: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.include = include.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl Default for ServerPackOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 判定 Mod 仅客户端的依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientOnlySource {
+    /// jar 中的 `fabric.mod.json`、`quilt.mod.json` 或 `mods.toml`
+    Metadata,
+    /// Modrinth 项目的 `server_side`
+    Modrinth,
+}
+
+/// 未复制到服务端的仅客户端 Mod
+#[derive(Debug, Clone)]
+pub struct ClientOnlyMod {
+    pub file: String,
+    pub source: ClientOnlySource,
+}
+
+/// 服务端包的生成结果
+#[derive(Debug)]
+pub struct ServerPackReport {
+    pub install: ServerInstall,
+    /// 复制到服务端的 Mod 文件名
+    pub mods: Vec<String>,
+    pub skipped: Vec<ClientOnlyMod>,
+}
+
+impl InstanceManager {
+    /// 从客户端实例生成可直接运行的服务端目录
+    ///
+    /// 安装与实例相同的服务端加载器，复制非仅客户端的 Mod 与选中的配置，
+    /// 并按实例的内存与 JVM 参数写入 `start.sh`/`start.bat`。提供
+    /// `modrinth` 时还会参考 Modrinth 项目的 `server_side`。首次启动前仍需
+    /// 同意 EULA。
+    pub async fn create_server_pack<P>(
+        &self,
+        id: &str,
+        dest: &Path,
+        options: &ServerPackOptions,
+        provider: &P,
+        java: &Path,
+        modrinth: Option<&ModrinthPlatform>,
+    ) -> Result<ServerPackReport>
+    where
+        P: DownloadProvider<Error = unml_download::Error>,
+    {
+        let instance = self.load(id).await?;
+        let game_dir = self.game_dir(id);
+
+        let (mods, skipped) = self.server_mods(id, modrinth).await?;
+
+        let server = DedicatedServer::new(dest);
+        let install = server
+            .install(
+                provider,
+                &instance.game_version,
+                instance.loader.as_ref(),
+                java,
+            )
+            .await?;
+
+        if !mods.is_empty() {
+            tokio::fs::create_dir_all(dest.join("mods")).await?;
+        }
+        for name in &mods {
+            tokio::fs::copy(
+                game_dir.join("mods").join(name),
+                dest.join("mods").join(name),
+            )
+            .await?;
+        }
+
+        for path in &options.include {
+            let (Some(source), Some(target)) = (safe_join(&game_dir, path), safe_join(dest, path))
+            else {
+                continue;
+            };
+            let Ok(metadata) = tokio::fs::metadata(&source).await else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                copy_dir(&source, &target).await?;
+            } else {
+                if let Some(parent) = target.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::copy(&source, &target).await?;
+            }
+        }
+
+        write_start_scripts(dest, &install, &instance.settings).await?;
+
+        Ok(ServerPackReport {
+            install,
+            mods,
+            skipped,
+        })
+    }
+
+    /// 生成服务端包并打包为 zip
+    pub async fn create_server_pack_zip<P>(
+        &self,
+        id: &str,
+        dest: &Path,
+        options: &ServerPackOptions,
+        provider: &P,
+        java: &Path,
+        modrinth: Option<&ModrinthPlatform>,
+    ) -> Result<ServerPackReport>
+    where
+        P: DownloadProvider<Error = unml_download::Error>,
+    {
+        let staging = self.instance_dir(id).join(SERVER_PACK_DIR);
+        if tokio::fs::try_exists(&staging).await? {
+            tokio::fs::remove_dir_all(&staging).await?;
+        }
+
+        let result = async {
+            let report = self
+                .create_server_pack(id, &staging, options, provider, java, modrinth)
+                .await?;
+            let entries = walk_files(&staging, vec![staging.clone()])
+                .await?
+                .into_iter()
+                .map(|(relative, path)| (relative, ZipEntry::File(path)))
+                .collect();
+            write_zip(dest, entries).await?;
+            Ok(report)
+        }
+        .await;
+
+        // 生成失败时可能还没有创建临时目录，清理失败也不应掩盖原来的错误
+        match tokio::fs::remove_dir_all(&staging).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound && result.is_ok() => Err(e.into()),
+            _ => result,
+        }
+    }
+
+    /// 区分 `mods/` 中要复制到服务端的 Mod 与仅客户端的 Mod
+    async fn server_mods(
+        &self,
+        id: &str,
+        modrinth: Option<&ModrinthPlatform>,
+    ) -> Result<(Vec<String>, Vec<ClientOnlyMod>)> {
+        let mods_dir = self.game_dir(id).join("mods");
+        let mut mods = Vec::new();
+        let mut skipped = Vec::new();
+        if !tokio::fs::try_exists(&mods_dir).await? {
+            return Ok((mods, skipped));
+        }
+
+        let mut entries = tokio::fs::read_dir(&mods_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.ends_with(".jar") || !entry.file_type().await?.is_file() {
+                continue;
+            }
+
+            // 无法读取元数据的 jar 按两侧通用处理
+            if let Ok(Some(PackSide::Client)) = mod_side(&entry.path()).await {
+                skipped.push(ClientOnlyMod {
+                    file: name,
+                    source: ClientOnlySource::Metadata,
+                });
+            } else {
+                mods.push(name);
+            }
+        }
+
+        if let Some(modrinth) = modrinth {
+            // 无法读取的文件不参与查询，按两侧通用处理
+            let mut hashed = Vec::new();
+            for name in &mods {
+                if let Some(sha1) = file_sha1(&mods_dir.join(name)).await {
+                    hashed.push((name.clone(), sha1));
+                }
+            }
+            let hashes: Vec<String> = hashed.iter().map(|(_, sha1)| sha1.clone()).collect();
+            let versions = modrinth.get_versions_by_sha1(&hashes).await?;
+
+            let project_ids: Vec<String> =
+                versions.values().map(|v| v.project_id.clone()).collect();
+            let client_only: Vec<String> = modrinth
+                .get_projects(&project_ids)
+                .await?
+                .into_iter()
+                .filter(|project| project.server_side == EnvSupport::Unsupported)
+                .map(|project| project.id)
+                .collect();
+
+            let client: Vec<String> = hashed
+                .into_iter()
+                .filter(|(_, sha1)| {
+                    versions
+                        .get(sha1)
+                        .is_some_and(|version| client_only.contains(&version.project_id))
+                })
+                .map(|(name, _)| name)
+                .collect();
+            mods.retain(|name| !client.contains(name));
+            skipped.extend(client.into_iter().map(|name| ClientOnlyMod {
+                file: name,
+                source: ClientOnlySource::Modrinth,
+            }));
+        }
+
+        mods.sort();
+        skipped.sort_by(|a, b| a.file.cmp(&b.file));
+
+        Ok((mods, skipped))
+    }
+}
+
+/// 写入使用实例内存与 JVM 参数的启动脚本，可通过 `JAVA` 环境变量指定 Java
+async fn write_start_scripts(
+    dest: &Path,
+    install: &ServerInstall,
+    settings: &InstanceSettings,
+) -> Result<()> {
+    let mut jvm_args = Vec::new();
+    if let Some(min) = settings.min_memory_mb {
+        jvm_args.push(format!("-Xms{min}M"));
+    }
+    if let Some(max) = settings.max_memory_mb {
+        jvm_args.push(format!("-Xmx{max}M"));
+    }
+    jvm_args.extend(settings.jvm_args.iter().cloned());
+
+    let unix: Vec<String> = jvm_args
+        .iter()
+        .cloned()
+        .chain(install.launch.args(false))
+        .map(|arg| sh_quote(&arg))
+        .collect();
+    let script = format!(
+        "#!/bin/sh\ncd \"$(dirname \"$0\")\"\nexec \"${{JAVA:-java}}\" {} nogui \"$@\"\n",
+        unix.join(" ")
+    );
+    let sh = dest.join("start.sh");
+    tokio::fs::write(&sh, script).await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&sh, std::fs::Permissions::from_mode(0o755)).await?;
+    }
+
+    let windows: Vec<String> = jvm_args
+        .into_iter()
+        .chain(install.launch.args(true))
+        .map(|arg| bat_quote(&arg))
+        .collect();
+    let script = format!(
+        "@echo off\r\ncd /d \"%~dp0\"\r\nif \"%JAVA%\"==\"\" set JAVA=java\r\n\"%JAVA%\" {} nogui %*\r\npause\r\n",
+        windows.join(" ")
+    );
+    tokio::fs::write(dest.join("start.bat"), script).await?;
+
+    Ok(())
+}
+
+fn sh_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_=.,:/@+%".contains(c))
+    {
+        return arg.to_owned();
+    }
+
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// 按 Windows 命令行规则引用参数，并转义批处理中的 `%` 与引号外的特殊字符
+fn bat_quote(arg: &str) -> String {
+    let quoted = if arg.is_empty() || arg.contains([' ', '\t', '&', '|', '<', '>', '^', '"']) {
+        let mut quoted = String::from('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                // 引号前的反斜杠加倍，引号本身写作 \"
+                '"' => {
+                    quoted.push_str(&"\\".repeat(backslashes + 1));
+                    backslashes = 0;
+                }
+                _ => backslashes = 0,
+            }
+            quoted.push(c);
+        }
+        // 结尾的反斜杠会转义右引号，同样需要加倍
+        quoted.push_str(&"\\".repeat(backslashes));
+        quoted.push('"');
+        quoted
+    } else {
+        arg.to_owned()
+    };
+
+    // cmd 遇到每个 `"` 都会切换引号状态，\" 之后的内容会被视为在引号外
+    let mut escaped = String::with_capacity(quoted.len());
+    let mut in_quotes = false;
+    for c in quoted.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '%' => escaped.push('%'),
+            '&' | '|' | '<' | '>' | '^' if !in_quotes => escaped.push('^'),
+            _ => {}
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bat_quoting() {
+        assert_eq!(bat_quote("-Xmx4G"), "-Xmx4G");
+        assert_eq!(bat_quote(""), r#""""#);
+        assert_eq!(bat_quote("-Dname=a b"), r#""-Dname=a b""#);
+        assert_eq!(bat_quote("-Dpercent=50%"), "-Dpercent=50%%");
+        assert_eq!(bat_quote("-Dpath=C:\\dir\\"), "-Dpath=C:\\dir\\");
+        // 第二个引号之后 cmd 认为处于引号外
+        assert_eq!(bat_quote(r#"-Dmotd="a&b""#), r#""-Dmotd=\"a^&b\"""#);
+        assert_eq!(bat_quote(r#"-Dx="a" & b"#), r#""-Dx=\"a\" & b""#);
+        assert_eq!(bat_quote("a b\\"), r#""a b\\""#);
+    }
+}
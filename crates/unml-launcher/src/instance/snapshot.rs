@@ -0,0 +1,137 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use unml_mods::safe_join;
+
+use super::{INSTANCE_FILE, InstanceManager, PACK_FILES, copy_dir};
+use crate::{Error, Result};
+
+const SNAPSHOT_DIR: &str = "snapshots";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+/// 快照中备份的游戏文件所在目录
+const FILES_DIR: &str = "files";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotManifest {
+    /// 创建时间（Unix 秒）
+    created: u64,
+    /// 快照之后新增的文件，恢复时删除
+    added: Vec<String>,
+}
+
+impl InstanceManager {
+    /// 实例的快照，最新的在前
+    pub async fn list_snapshots(&self, id: &str) -> Result<Vec<String>> {
+        let dir = self.instance_dir(id).join(SNAPSHOT_DIR);
+        let mut snapshots = Vec::new();
+        if !tokio::fs::try_exists(&dir).await? {
+            return Ok(snapshots);
+        }
+
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if tokio::fs::try_exists(entry.path().join(SNAPSHOT_FILE)).await? {
+                snapshots.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        snapshots.sort_by(|a, b| b.cmp(a));
+
+        Ok(snapshots)
+    }
+
+    /// 把实例恢复到快照时的状态，快照本身保留
+    pub async fn restore_snapshot(&self, id: &str, name: &str) -> Result<()> {
+        let dir = safe_join(&self.instance_dir(id).join(SNAPSHOT_DIR), name)
+            .ok_or_else(|| Error::SnapshotNotFound(name.to_owned()))?;
+        let manifest = match tokio::fs::read(dir.join(SNAPSHOT_FILE)).await {
+            Ok(data) => {
+                serde_json::from_slice::<SnapshotManifest>(&data).map_err(unml_core::JsonError)?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::SnapshotNotFound(name.to_owned()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let game_dir = self.game_dir(id);
+        for path in &manifest.added {
+            let Some(path) = safe_join(&game_dir, path) else {
+                continue;
+            };
+            match tokio::fs::remove_file(path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        let files = dir.join(FILES_DIR);
+        if tokio::fs::try_exists(&files).await? {
+            copy_dir(&files, &game_dir).await?;
+        }
+
+        let instance_dir = self.instance_dir(id);
+        tokio::fs::copy(dir.join(INSTANCE_FILE), instance_dir.join(INSTANCE_FILE)).await?;
+        if tokio::fs::try_exists(dir.join(PACK_FILES)).await? {
+            tokio::fs::copy(dir.join(PACK_FILES), instance_dir.join(PACK_FILES)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 备份 `changed` 中将被覆盖或删除的文件，返回快照名
+    ///
+    /// `added` 中的文件在快照之后才会出现，恢复时删除。
+    pub(super) async fn create_snapshot(
+        &self,
+        id: &str,
+        changed: &[String],
+        added: &[String],
+    ) -> Result<String> {
+        let created = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let root = self.instance_dir(id).join(SNAPSHOT_DIR);
+
+        let mut name = created.to_string();
+        let mut suffix = 2;
+        while tokio::fs::try_exists(root.join(&name)).await? {
+            name = format!("{created}-{suffix}");
+            suffix += 1;
+        }
+        let dir = root.join(&name);
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let instance_dir = self.instance_dir(id);
+        tokio::fs::copy(instance_dir.join(INSTANCE_FILE), dir.join(INSTANCE_FILE)).await?;
+        if tokio::fs::try_exists(instance_dir.join(PACK_FILES)).await? {
+            tokio::fs::copy(instance_dir.join(PACK_FILES), dir.join(PACK_FILES)).await?;
+        }
+
+        let game_dir = self.game_dir(id);
+        for path in changed {
+            let (Some(source), Some(target)) = (
+                safe_join(&game_dir, path),
+                safe_join(&dir.join(FILES_DIR), path),
+            ) else {
+                continue;
+            };
+            if !tokio::fs::try_exists(&source).await? {
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::copy(source, target).await?;
+        }
+
+        let manifest = SnapshotManifest {
+            created,
+            added: added.to_vec(),
+        };
+        let data = serde_json::to_vec_pretty(&manifest).map_err(unml_core::JsonError)?;
+        tokio::fs::write(dir.join(SNAPSHOT_FILE), data).await?;
+
+        Ok(name)
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use unml_core::{Instance, LoaderSpec, PackFormat, PackInfo, ProgressCallback, file_sha1};
+use unml_mods::{CurseForgePack, CurseForgePlatform, ManualDownload, Mrpack, PackSide};
+
+use super::{InstanceManager, walk_files};
+use crate::{Error, Result};
+
+/// 新版本整合包的临时安装目录（位于实例目录中）
+const UPDATE_DIR: &str = ".update";
+/// 冲突时新版本文件的后缀
+const CONFLICT_SUFFIX: &str = ".new";
+
+/// 更新时用户修改与整合包修改冲突的文件
+#[derive(Debug, Clone)]
+pub struct UpdateConflict {
+    pub path: String,
+    pub kind: ConflictKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// 双方都修改了文件，保留用户的版本，新版本另存为 `<path>.new`
+    BothModified,
+    /// 用户修改了文件，新版本整合包删除了它，保留用户的版本
+    ModifiedDeleted,
+    /// 用户删除了文件，新版本整合包修改了它，保持删除
+    DeletedModified,
+}
+
+/// 整合包更新的结果
+#[derive(Debug)]
+pub struct UpdateReport {
+    pub instance: Instance,
+    /// 更新前创建的快照，可用 [`InstanceManager::restore_snapshot`] 回滚
+    pub snapshot: String,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// 整合包未改动、保留了用户修改的文件
+    pub kept: Vec<String>,
+    pub conflicts: Vec<UpdateConflict>,
+    /// 需要用户手动下载的文件
+    pub manual: Vec<ManualDownload>,
+}
+
+/// 对单个文件的处理
+enum Change {
+    Write,
+    Remove,
+    /// 冲突时把新版本另存
+    WriteAside,
+}
+
+impl InstanceManager {
+    /// 用新版本 `.mrpack` 更新由 Modrinth 整合包创建的实例
+    ///
+    /// 新版本需要不同的加载器时返回
+    /// [`Error::LoaderInstallUnsupported`]，实例保持不变。
+    pub async fn update_mrpack(
+        &self,
+        id: &str,
+        path: impl AsRef<Path>,
+        progress: Option<ProgressCallback>,
+    ) -> Result<UpdateReport> {
+        let mut instance = self.load(id).await?;
+        check_pack(&instance, PackFormat::Modrinth)?;
+
+        let pack = Mrpack::open(path.as_ref()).await?;
+        let index = pack.index();
+        let game_version = index
+            .game_version()
+            .ok_or(Error::MissingGameVersion)?
+            .to_owned();
+        check_loader(&instance, index.loader())?;
+        let staging = self.prepare_staging(id).await?;
+
+        let result = async {
+            let failed = pack
+                .download_files(&staging, PackSide::Client, progress)
+                .await;
+            if !failed.is_empty() {
+                return Err(Error::UpdateFailed(failed));
+            }
+            pack.extract_overrides(&staging, PackSide::Client).await?;
+
+            instance.game_version = game_version;
+            instance.loader = index.loader();
+            instance.pack = Some(PackInfo {
+                format: PackFormat::Modrinth,
+                name: index.name.clone(),
+                version: index.version_id.clone(),
+            });
+
+            self.apply_update(instance, &staging, Vec::new()).await
+        }
+        .await;
+
+        remove_staging(&staging, result).await
+    }
+
+    /// 用新版本 CurseForge 整合包更新由 CurseForge 整合包创建的实例
+    ///
+    /// 新版本需要不同的加载器时返回
+    /// [`Error::LoaderInstallUnsupported`]，实例保持不变。
+    pub async fn update_curseforge(
+        &self,
+        id: &str,
+        path: impl AsRef<Path>,
+        platform: &CurseForgePlatform,
+        progress: Option<ProgressCallback>,
+    ) -> Result<UpdateReport> {
+        let mut instance = self.load(id).await?;
+        check_pack(&instance, PackFormat::CurseForge)?;
+
+        let pack = CurseForgePack::open(path.as_ref()).await?;
+        let manifest = pack.manifest();
+        check_loader(&instance, manifest.loader())?;
+        let staging = self.prepare_staging(id).await?;
+
+        let result = async {
+            let install = pack.install_files(platform, &staging, progress).await?;
+            if !install.failed.is_empty() {
+                return Err(Error::UpdateFailed(install.failed));
+            }
+            pack.extract_overrides(&staging).await?;
+
+            instance.game_version = manifest.minecraft.version.clone();
+            instance.loader = manifest.loader();
+            instance.pack = Some(PackInfo {
+                format: PackFormat::CurseForge,
+                name: manifest.name.clone(),
+                version: manifest.version.clone(),
+            });
+
+            self.apply_update(instance, &staging, install.manual).await
+        }
+        .await;
+
+        remove_staging(&staging, result).await
+    }
+
+    async fn prepare_staging(&self, id: &str) -> Result<PathBuf> {
+        let staging = self.instance_dir(id).join(UPDATE_DIR);
+        if tokio::fs::try_exists(&staging).await? {
+            tokio::fs::remove_dir_all(&staging).await?;
+        }
+        tokio::fs::create_dir_all(&staging).await?;
+
+        Ok(staging)
+    }
+
+    /// 以上次安装的文件为基准，对比新版本与当前文件做三方合并
+    ///
+    /// 用户未改动的文件跟随整合包更新，整合包未改动的文件保留用户修改，
+    /// 双方都改动的文件记为冲突。用户自己添加的文件不受影响。
+    async fn apply_update(
+        &self,
+        instance: Instance,
+        staging: &Path,
+        manual: Vec<ManualDownload>,
+    ) -> Result<UpdateReport> {
+        let id = instance.id.clone();
+        let game_dir = self.game_dir(&id);
+
+        let base = self.load_pack_files(&id).await?;
+        let mut theirs = HashMap::new();
+        for (relative, path) in walk_files(staging, vec![staging.to_path_buf()]).await? {
+            if let Some(sha1) = file_sha1(&path).await {
+                theirs.insert(relative, sha1);
+            }
+        }
+
+        // 需要手动下载的文件不在临时目录中，保持原样
+        let skipped: HashSet<&str> = manual.iter().map(|m| m.path.as_str()).collect();
+
+        let mut report = UpdateReport {
+            instance,
+            snapshot: String::new(),
+            added: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+            kept: Vec::new(),
+            conflicts: Vec::new(),
+            manual: Vec::new(),
+        };
+        let mut changes = Vec::new();
+        let paths: BTreeSet<&String> = base.keys().chain(theirs.keys()).collect();
+        for path in paths {
+            if skipped.contains(path.as_str()) {
+                continue;
+            }
+
+            let ours = file_sha1(&game_dir.join(path)).await;
+            let (ours, base, theirs) = (ours.as_ref(), base.get(path), theirs.get(path));
+            if ours == theirs {
+                continue;
+            }
+
+            if ours == base {
+                match (ours, theirs) {
+                    (_, None) => {
+                        changes.push((path.clone(), Change::Remove));
+                        report.removed.push(path.clone());
+                    }
+                    (None, Some(_)) => {
+                        changes.push((path.clone(), Change::Write));
+                        report.added.push(path.clone());
+                    }
+                    (Some(_), Some(_)) => {
+                        changes.push((path.clone(), Change::Write));
+                        report.updated.push(path.clone());
+                    }
+                }
+            } else if base == theirs {
+                report.kept.push(path.clone());
+            } else {
+                let kind = match (ours, theirs) {
+                    (Some(_), None) => ConflictKind::ModifiedDeleted,
+                    (None, _) => ConflictKind::DeletedModified,
+                    (Some(_), Some(_)) => {
+                        changes.push((path.clone(), Change::WriteAside));
+                        ConflictKind::BothModified
+                    }
+                };
+                report.conflicts.push(UpdateConflict {
+                    path: path.clone(),
+                    kind,
+                });
+            }
+        }
+
+        let changed: Vec<String> = report
+            .updated
+            .iter()
+            .chain(&report.removed)
+            .cloned()
+            .collect();
+        let added: Vec<String> = report
+            .added
+            .iter()
+            .cloned()
+            .chain(
+                report
+                    .conflicts
+                    .iter()
+                    .filter(|c| c.kind == ConflictKind::BothModified)
+                    .map(|c| format!("{}{CONFLICT_SUFFIX}", c.path)),
+            )
+            .collect();
+        report.snapshot = self.create_snapshot(&id, &changed, &added).await?;
+
+        for (path, change) in changes {
+            let target = match change {
+                Change::Remove => {
+                    tokio::fs::remove_file(game_dir.join(&path)).await?;
+                    continue;
+                }
+                Change::Write => game_dir.join(&path),
+                Change::WriteAside => game_dir.join(format!("{path}{CONFLICT_SUFFIX}")),
+            };
+
+            if let Some(parent) = target.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::copy(staging.join(&path), target).await?;
+        }
+
+        // 记录新版本的文件，手动下载的文件沿用旧记录
+        for path in skipped {
+            if let Some(sha1) = base.get(path) {
+                theirs.insert(path.to_owned(), sha1.clone());
+            }
+        }
+        self.save_pack_files(&id, &theirs).await?;
+        self.save(&report.instance).await?;
+
+        report.manual = manual;
+
+        Ok(report)
+    }
+}
+
+/// 启动器尚不能安装客户端加载器，新版本更换或新增加载器时拒绝更新
+fn check_loader(instance: &Instance, loader: Option<LoaderSpec>) -> Result<()> {
+    match loader {
+        Some(loader) if instance.loader.as_ref() != Some(&loader) => Err(
+            Error::LoaderInstallUnsupported(format!("{:?} {}", loader.loader, loader.version)),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// 删除临时目录，清理失败也不应掩盖更新本身的错误
+async fn remove_staging<T>(staging: &Path, result: Result<T>) -> Result<T> {
+    match tokio::fs::remove_dir_all(staging).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound && result.is_ok() => Err(e.into()),
+        _ => result,
+    }
+}
+
+fn check_pack(instance: &Instance, format: PackFormat) -> Result<()> {
+    match &instance.pack {
+        Some(pack) if pack.format == format => Ok(()),
+        _ => Err(Error::PackMismatch(
+            match format {
+                PackFormat::Modrinth => "Modrinth",
+                PackFormat::CurseForge => "CurseForge",
+            }
+            .to_owned(),
+        )),
+    }
+}
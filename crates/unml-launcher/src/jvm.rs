@@ -0,0 +1,7 @@
+mod memory;
+mod preset;
+mod validate;
+
+pub use memory::{MemoryInfo, count_mods, recommended_heap};
+pub use preset::JvmPreset;
+pub use validate::{JvmArgIssue, merge_jvm_args, validate_jvm_args};
@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+use unml_java::JavaInstallation;
+
+const MIB: u64 = 1024 * 1024;
+
+/// 原版推荐的最大堆
+const VANILLA_HEAP_MB: u32 = 2048;
+/// 有 Mod 时的基础堆，另按 Mod 数量增加
+const MODDED_BASE_HEAP_MB: u32 = 3072;
+const HEAP_PER_MOD_MB: u32 = 24;
+const MAX_RECOMMENDED_HEAP_MB: u32 = 12288;
+const MIN_HEAP_MB: u32 = 1024;
+/// 32 位 JVM 的地址空间有限，推荐值保守一些
+const MAX_32BIT_RECOMMENDED_HEAP_MB: u32 = 1024;
+/// 给操作系统和其他程序保留的内存
+const SYSTEM_RESERVED_MB: u32 = 2048;
+
+/// 系统内存（MiB）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryInfo {
+    pub total_mb: u32,
+    pub available_mb: u32,
+}
+
+impl MemoryInfo {
+    pub fn current() -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing().with_memory(MemoryRefreshKind::nothing().with_ram()),
+        );
+
+        Self {
+            total_mb: to_mb(system.total_memory()),
+            available_mb: to_mb(system.available_memory()),
+        }
+    }
+}
+
+fn to_mb(bytes: u64) -> u32 {
+    u32::try_from(bytes / MIB).unwrap_or(u32::MAX)
+}
+
+/// 根据系统内存、Mod 数量和 Java 架构计算推荐的最大堆（MiB）
+///
+/// 结果不超过总内存的 3/4，也尽量不超过当前可用内存，并按 256 MiB 对齐。
+pub fn recommended_heap(memory: MemoryInfo, mod_count: usize, java: &JavaInstallation) -> u32 {
+    let wanted = if mod_count == 0 {
+        VANILLA_HEAP_MB
+    } else {
+        let mods = u32::try_from(mod_count).unwrap_or(u32::MAX);
+        MODDED_BASE_HEAP_MB
+            .saturating_add(mods.saturating_mul(HEAP_PER_MOD_MB))
+            .min(MAX_RECOMMENDED_HEAP_MB)
+    };
+
+    let limit = (memory.total_mb / 4 * 3)
+        .min(memory.total_mb.saturating_sub(SYSTEM_RESERVED_MB))
+        .min(memory.available_mb.max(MIN_HEAP_MB));
+
+    let mut heap = wanted.min(limit).max(MIN_HEAP_MB);
+    if !is_64bit(java) {
+        heap = heap.min(MAX_32BIT_RECOMMENDED_HEAP_MB);
+    }
+
+    heap / 256 * 256
+}
+
+pub(crate) fn is_64bit(java: &JavaInstallation) -> bool {
+    java.arch.as_deref().is_none_or(|arch| arch.contains("64"))
+}
+
+/// 统计 mods 目录中启用的 Mod 数量
+pub fn count_mods(mods_dir: &Path) -> usize {
+    std::fs::read_dir(mods_dir).map_or(0, |entries| {
+        entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .path()
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+            })
+            .count()
+    })
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use unml_java::JavaInstallation;
+
+/// 预设的 JVM 参数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JvmPreset {
+    /// G1，与官方启动器接近
+    #[default]
+    Default,
+    /// Aikar's flags，为 G1 调优，适合大型整合包
+    Aikar,
+    /// 分代 ZGC，停顿极短，需要 Java 21+
+    Zgc,
+    /// 串行 GC，适合内存较小的机器
+    LowMemory,
+}
+
+impl JvmPreset {
+    pub const ALL: [Self; 4] = [Self::Default, Self::Aikar, Self::Zgc, Self::LowMemory];
+
+    /// 所需的最低 Java 主版本
+    pub fn min_java(self) -> u32 {
+        match self {
+            Self::Zgc => 21,
+            _ => 8,
+        }
+    }
+
+    pub fn supports(self, java: &JavaInstallation) -> bool {
+        java.major_version >= self.min_java()
+    }
+
+    /// 生成包含堆大小的完整参数
+    pub fn args(self, heap_mb: u32, java: &JavaInstallation) -> Vec<String> {
+        let mut args = vec![format!("-Xmx{heap_mb}m")];
+
+        let flags: &[&str] = match self {
+            Self::Default => &[
+                "-XX:+UseG1GC",
+                "-XX:+ParallelRefProcEnabled",
+                "-XX:MaxGCPauseMillis=200",
+            ],
+            Self::Aikar => {
+                // Aikar's flags 要求初始堆与最大堆一致
+                args.push(format!("-Xms{heap_mb}m"));
+                args.extend(AIKAR.iter().map(|&flag| flag.to_owned()));
+                if heap_mb > 12 * 1024 {
+                    AIKAR_LARGE
+                } else {
+                    AIKAR_REGULAR
+                }
+            }
+            Self::Zgc => {
+                args.push("-XX:+UseZGC".to_owned());
+                // Java 23 起分代模式成为默认，该参数被废弃
+                if java.major_version < 23 {
+                    args.push("-XX:+ZGenerational".to_owned());
+                }
+                &[]
+            }
+            Self::LowMemory => {
+                // 初始堆不能超过最大堆
+                let initial = (heap_mb / 2).max(512).min(heap_mb);
+                args.push(format!("-Xms{initial}m"));
+                &[
+                    "-XX:+UseSerialGC",
+                    "-XX:MinHeapFreeRatio=10",
+                    "-XX:MaxHeapFreeRatio=20",
+                ]
+            }
+        };
+        args.extend(flags.iter().map(|&flag| flag.to_owned()));
+
+        args
+    }
+}
+
+const AIKAR: &[&str] = &[
+    "-XX:+UseG1GC",
+    "-XX:+ParallelRefProcEnabled",
+    "-XX:MaxGCPauseMillis=200",
+    "-XX:+UnlockExperimentalVMOptions",
+    "-XX:+DisableExplicitGC",
+    "-XX:+AlwaysPreTouch",
+    "-XX:G1HeapWastePercent=5",
+    "-XX:G1MixedGCCountTarget=4",
+    "-XX:G1MixedGCLiveThresholdPercent=90",
+    "-XX:G1RSetUpdatingPauseTimePercent=5",
+    "-XX:SurvivorRatio=32",
+    "-XX:+PerfDisableSharedMem",
+    "-XX:MaxTenuringThreshold=1",
+];
+
+const AIKAR_REGULAR: &[&str] = &[
+    "-XX:G1NewSizePercent=30",
+    "-XX:G1MaxNewSizePercent=40",
+    "-XX:G1HeapRegionSize=8M",
+    "-XX:G1ReservePercent=20",
+    "-XX:InitiatingHeapOccupancyPercent=15",
+];
+
+/// 超过 12G 堆时 Aikar 推荐的调整
+const AIKAR_LARGE: &[&str] = &[
+    "-XX:G1NewSizePercent=40",
+    "-XX:G1MaxNewSizePercent=50",
+    "-XX:G1HeapRegionSize=16M",
+    "-XX:G1ReservePercent=15",
+    "-XX:InitiatingHeapOccupancyPercent=20",
+];
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use unml_java::JavaSource;
+
+    use super::*;
+    use crate::validate_jvm_args;
+
+    fn java(major_version: u32) -> JavaInstallation {
+        JavaInstallation {
+            executable: PathBuf::from("/opt/java/bin/java"),
+            version: format!("{major_version}.0.1"),
+            major_version,
+            vendor: None,
+            vm_name: None,
+            home: PathBuf::from("/opt/java"),
+            arch: Some("amd64".to_owned()),
+            source: JavaSource::System,
+        }
+    }
+
+    #[test]
+    fn presets_pass_validation() {
+        let java = java(21);
+        for preset in JvmPreset::ALL {
+            for heap_mb in [256, 1024, 16 * 1024] {
+                let args = preset.args(heap_mb, &java);
+                assert_eq!(validate_jvm_args(&args, &java), [], "{preset:?} {heap_mb}m");
+            }
+        }
+    }
+
+    #[test]
+    fn low_memory_initial_heap() {
+        let java = java(8);
+        assert!(
+            JvmPreset::LowMemory
+                .args(256, &java)
+                .contains(&"-Xms256m".to_owned())
+        );
+        assert!(
+            JvmPreset::LowMemory
+                .args(4096, &java)
+                .contains(&"-Xms2048m".to_owned())
+        );
+    }
+}
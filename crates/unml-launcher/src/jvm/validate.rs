@@ -0,0 +1,369 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use unml_java::JavaInstallation;
+
+use super::memory::is_64bit;
+
+/// 32 位 JVM 能分配的最大堆（MiB）
+const MAX_32BIT_HEAP_MB: u64 = 1536;
+
+/// 互斥的垃圾回收器开关
+const GC_FLAGS: &[&str] = &[
+    "UseG1GC",
+    "UseZGC",
+    "UseShenandoahGC",
+    "UseParallelGC",
+    "UseSerialGC",
+    "UseConcMarkSweepGC",
+    "UseEpsilonGC",
+];
+
+/// 用户 JVM 参数中的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JvmArgIssue {
+    /// 同一参数出现多次
+    Duplicate(String),
+    /// 同时启用了多个垃圾回收器
+    ConflictingGc(Vec<String>),
+    /// 所选 Java 不支持该参数
+    Unsupported { arg: String, reason: String },
+    /// 堆大小无效，如 `-Xms` 大于 `-Xmx`
+    InvalidHeap(String),
+}
+
+impl JvmArgIssue {
+    /// 游戏无法以这些参数启动，应在启动前拦截
+    pub fn blocks_launch(&self) -> bool {
+        matches!(self, Self::ConflictingGc(_) | Self::Unsupported { .. })
+    }
+}
+
+impl fmt::Display for JvmArgIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate(arg) => write!(f, "{arg} is specified more than once"),
+            Self::ConflictingGc(gcs) => {
+                write!(f, "conflicting garbage collectors: {}", gcs.join(", "))
+            }
+            Self::Unsupported { arg, reason } => write!(f, "{arg} is not supported: {reason}"),
+            Self::InvalidHeap(reason) => write!(f, "invalid heap size: {reason}"),
+        }
+    }
+}
+
+/// 检查用户提供的 JVM 参数，返回发现的所有问题
+pub fn validate_jvm_args(args: &[String], java: &JavaInstallation) -> Vec<JvmArgIssue> {
+    let mut issues = Vec::new();
+
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for arg in args {
+        if let Some(key) = arg_key(arg)
+            && seen.insert(key, arg).is_some()
+        {
+            issues.push(JvmArgIssue::Duplicate(arg.clone()));
+        }
+    }
+
+    // 重复的同一回收器已作为 Duplicate 报告
+    let mut gcs: Vec<String> = Vec::new();
+    for flag in args.iter().filter_map(|arg| arg.strip_prefix("-XX:+")) {
+        if GC_FLAGS.contains(&flag) && !gcs.iter().any(|gc| gc == flag) {
+            gcs.push(flag.to_owned());
+        }
+    }
+    if gcs.len() > 1 {
+        issues.push(JvmArgIssue::ConflictingGc(gcs));
+    }
+
+    for arg in args {
+        if let Some(reason) = unsupported_reason(arg, java) {
+            issues.push(JvmArgIssue::Unsupported {
+                arg: arg.clone(),
+                reason,
+            });
+        }
+    }
+
+    let mut size = |prefix| {
+        last_size(args, prefix).unwrap_or_else(|issue| {
+            issues.push(issue);
+            None
+        })
+    };
+    let max_heap = size("-Xmx");
+    let min_heap = size("-Xms");
+    if let (Some(min), Some(max)) = (min_heap, max_heap)
+        && min > max
+    {
+        issues.push(JvmArgIssue::InvalidHeap(
+            "-Xms is larger than -Xmx".to_owned(),
+        ));
+    }
+    if let Some(max) = max_heap
+        && !is_64bit(java)
+        && max > MAX_32BIT_HEAP_MB * 1024 * 1024
+    {
+        issues.push(JvmArgIssue::InvalidHeap(format!(
+            "32-bit Java cannot allocate more than {MAX_32BIT_HEAP_MB}m"
+        )));
+    }
+
+    issues
+}
+
+/// 把用户参数追加到预设参数之后
+///
+/// 预设中与用户参数同名的参数（如 `-Xmx`）被用户的值替换；
+/// 用户指定了垃圾回收器时去掉预设的回收器，避免二者冲突。
+pub fn merge_jvm_args(preset: Vec<String>, user: &[String]) -> Vec<String> {
+    let keys: HashSet<String> = user.iter().filter_map(|arg| arg_key(arg)).collect();
+    let user_gc = user.iter().any(|arg| is_gc_flag(arg));
+
+    preset
+        .into_iter()
+        .filter(|arg| !arg_key(arg).is_some_and(|key| keys.contains(&key)))
+        .filter(|arg| !(user_gc && is_gc_flag(arg)))
+        .chain(user.iter().cloned())
+        .collect()
+}
+
+fn is_gc_flag(arg: &str) -> bool {
+    arg.strip_prefix("-XX:+")
+        .is_some_and(|flag| GC_FLAGS.contains(&flag))
+}
+
+/// 用于判断重复的键：`-XX:+Foo`、`-XX:-Foo` 与 `-XX:Foo=1` 视为同一参数
+fn arg_key(arg: &str) -> Option<String> {
+    if let Some(flag) = arg.strip_prefix("-XX:") {
+        let flag = flag.trim_start_matches(['+', '-']);
+        let name = flag.split_once('=').map_or(flag, |(name, _)| name);
+        return Some(format!("-XX:{name}"));
+    }
+    if let Some(property) = arg.strip_prefix("-D") {
+        let name = property.split_once('=').map_or(property, |(name, _)| name);
+        return Some(format!("-D{name}"));
+    }
+    for prefix in ["-Xmx", "-Xms", "-Xss", "-Xmn"] {
+        if arg.starts_with(prefix) {
+            return Some(prefix.to_owned());
+        }
+    }
+
+    // -javaagent 等可以合法地出现多次
+    None
+}
+
+fn unsupported_reason(arg: &str, java: &JavaInstallation) -> Option<String> {
+    let major = java.major_version;
+    let openj9 = java
+        .vm_name
+        .as_deref()
+        .is_some_and(|vm| vm.contains("OpenJ9"));
+
+    let flag = arg.strip_prefix("-XX:+")?;
+    match flag {
+        _ if openj9 && GC_FLAGS.contains(&flag) => {
+            Some("OpenJ9 uses -Xgcpolicy instead of HotSpot GC flags".to_owned())
+        }
+        "UseZGC" if major < 15 => Some(format!("ZGC requires Java 15+, found Java {major}")),
+        "ZGenerational" if major < 21 => Some(format!(
+            "generational ZGC requires Java 21+, found Java {major}"
+        )),
+        "UseShenandoahGC" if major < 12 => {
+            Some(format!("Shenandoah requires Java 12+, found Java {major}"))
+        }
+        "UseConcMarkSweepGC" if major >= 14 => {
+            Some(format!("CMS was removed in Java 14, found Java {major}"))
+        }
+        _ => None,
+    }
+}
+
+/// 解析最后一个指定前缀的内存参数，返回字节数，格式错误或数值溢出时视为无效参数
+fn last_size(args: &[String], prefix: &str) -> Result<Option<u64>, JvmArgIssue> {
+    let Some(arg) = args.iter().rev().find(|arg| arg.starts_with(prefix)) else {
+        return Ok(None);
+    };
+    let value = &arg[prefix.len()..];
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" => 1,
+        "k" => 1024,
+        "m" => 1024 * 1024,
+        "g" => 1024 * 1024 * 1024,
+        _ => {
+            return Err(JvmArgIssue::InvalidHeap(format!(
+                "{arg} has an unknown unit"
+            )));
+        }
+    };
+    if number.is_empty() {
+        return Err(JvmArgIssue::InvalidHeap(format!("{arg} has no size")));
+    }
+
+    // 只含数字时解析失败说明超出了 u64
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .map(Some)
+        .ok_or_else(|| JvmArgIssue::InvalidHeap(format!("{arg} is too large")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use unml_java::JavaSource;
+
+    use super::*;
+
+    fn java(major_version: u32, arch: &str) -> JavaInstallation {
+        JavaInstallation {
+            executable: PathBuf::from("/opt/java/bin/java"),
+            version: format!("{major_version}.0.1"),
+            major_version,
+            vendor: None,
+            vm_name: Some("OpenJDK 64-Bit Server VM".to_owned()),
+            home: PathBuf::from("/opt/java"),
+            arch: Some(arch.to_owned()),
+            source: JavaSource::System,
+        }
+    }
+
+    fn validate(args: &[&str], java: &JavaInstallation) -> Vec<JvmArgIssue> {
+        let args: Vec<String> = args.iter().map(|&arg| arg.to_owned()).collect();
+        validate_jvm_args(&args, java)
+    }
+
+    #[test]
+    fn valid_args_have_no_issues() {
+        let issues = validate(
+            &["-Xmx4g", "-Xms2048m", "-XX:+UseG1GC", "-Dfoo=bar"],
+            &java(21, "amd64"),
+        );
+        assert_eq!(issues, []);
+    }
+
+    #[test]
+    fn duplicates() {
+        let issues = validate(
+            &["-XX:+UseG1GC", "-XX:+UseG1GC", "-Dfoo=1", "-Dfoo=2"],
+            &java(21, "amd64"),
+        );
+        assert_eq!(
+            issues,
+            [
+                JvmArgIssue::Duplicate("-XX:+UseG1GC".to_owned()),
+                JvmArgIssue::Duplicate("-Dfoo=2".to_owned()),
+            ]
+        );
+
+        let issues = validate(
+            &["-XX:+AlwaysPreTouch", "-XX:-AlwaysPreTouch"],
+            &java(21, "amd64"),
+        );
+        assert_eq!(
+            issues,
+            [JvmArgIssue::Duplicate("-XX:-AlwaysPreTouch".to_owned())]
+        );
+    }
+
+    #[test]
+    fn conflicting_gc() {
+        let issues = validate(&["-XX:+UseG1GC", "-XX:+UseZGC"], &java(21, "amd64"));
+        assert_eq!(
+            issues,
+            [JvmArgIssue::ConflictingGc(vec![
+                "UseG1GC".to_owned(),
+                "UseZGC".to_owned()
+            ])]
+        );
+    }
+
+    #[test]
+    fn unsupported_flags() {
+        let issues = validate(&["-XX:+UseZGC", "-XX:+ZGenerational"], &java(11, "amd64"));
+        assert!(matches!(
+            &issues[..],
+            [
+                JvmArgIssue::Unsupported { arg: zgc, .. },
+                JvmArgIssue::Unsupported { arg: generational, .. },
+            ] if zgc == "-XX:+UseZGC" && generational == "-XX:+ZGenerational"
+        ));
+
+        let issues = validate(&["-XX:+UseConcMarkSweepGC"], &java(17, "amd64"));
+        assert!(matches!(&issues[..], [JvmArgIssue::Unsupported { .. }]));
+        assert_eq!(
+            validate(&["-XX:+UseConcMarkSweepGC"], &java(8, "amd64")),
+            []
+        );
+
+        let mut openj9 = java(17, "amd64");
+        openj9.vm_name = Some("Eclipse OpenJ9 VM".to_owned());
+        let issues = validate(&["-XX:+UseG1GC"], &openj9);
+        assert!(matches!(&issues[..], [JvmArgIssue::Unsupported { .. }]));
+    }
+
+    #[test]
+    fn heap_sizes() {
+        let issues = validate(&["-Xmx1g", "-Xms2g"], &java(21, "amd64"));
+        assert!(matches!(&issues[..], [JvmArgIssue::InvalidHeap(_)]));
+
+        let issues = validate(&["-Xmx2g"], &java(8, "x86"));
+        assert!(matches!(&issues[..], [JvmArgIssue::InvalidHeap(_)]));
+        assert_eq!(validate(&["-Xmx1g"], &java(8, "x86")), []);
+
+        for arg in ["-Xmx4gb", "-Xmx4x", "-Xmx", "-Xmxg"] {
+            let issues = validate(&[arg], &java(21, "amd64"));
+            assert!(
+                matches!(&issues[..], [JvmArgIssue::InvalidHeap(_)]),
+                "{arg}: {issues:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn merge_overrides_preset() {
+        let preset = || {
+            ["-Xmx4096m", "-XX:+UseG1GC", "-XX:MaxGCPauseMillis=200"]
+                .map(str::to_owned)
+                .to_vec()
+        };
+
+        let user = ["-Xmx8g".to_owned(), "-Dfoo=bar".to_owned()];
+        assert_eq!(
+            merge_jvm_args(preset(), &user),
+            [
+                "-XX:+UseG1GC",
+                "-XX:MaxGCPauseMillis=200",
+                "-Xmx8g",
+                "-Dfoo=bar"
+            ]
+        );
+
+        let user = ["-XX:+UseZGC".to_owned()];
+        let merged = merge_jvm_args(preset(), &user);
+        assert_eq!(
+            merged,
+            ["-Xmx4096m", "-XX:MaxGCPauseMillis=200", "-XX:+UseZGC"]
+        );
+        assert_eq!(validate_jvm_args(&merged, &java(21, "amd64")), []);
+    }
+
+    #[test]
+    fn heap_overflow() {
+        for arg in ["-Xmx99999999999999999999", "-Xmx17179869184g"] {
+            let issues = validate(&[arg], &java(21, "amd64"));
+            assert_eq!(
+                issues,
+                [JvmArgIssue::InvalidHeap(format!("{arg} is too large"))]
+            );
+        }
+    }
+}
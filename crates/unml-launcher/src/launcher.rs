@@ -1,21 +1,100 @@
+mod arguments;
+mod authlib_injector;
+mod natives;
+
 use std::path::PathBuf;
 
+use arguments::LaunchArguments;
 use async_trait::async_trait;
-use unml_core::{Account, GameLauncher, GameProcess, LaunchConfig};
+pub use authlib_injector::ensure_authlib_injector;
+use tokio::process::Command;
+use unml_core::{
+    Account, AccountType, GameLauncher, GameProcess, GameRepository, LaunchConfig, VersionInfo,
+};
+use unml_java::JavaRequirement;
 
-use crate::{Error, Result};
+use crate::{Error, FileSystemRepository, Result, quick_play_args};
 
 pub struct StandardLauncher {
-    #[allow(dead_code)]
-    game_dir: PathBuf,
+    /// 版本、库与资源文件所在的 `.minecraft` 目录
+    root: PathBuf,
+    /// 游戏运行目录，未设置时与 `root` 相同
+    game_dir: Option<PathBuf>,
+    authlib_injector: Option<PathBuf>,
 }
 
 impl StandardLauncher {
     pub fn new() -> Self {
         Self {
-            game_dir: PathBuf::from("./minecraft"),
+            root: PathBuf::from("./minecraft"),
+            game_dir: None,
+            authlib_injector: None,
+        }
+    }
+
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// 版本隔离时每个实例使用独立的游戏目录
+    pub fn with_game_dir(mut self, game_dir: impl Into<PathBuf>) -> Self {
+        self.game_dir = Some(game_dir.into());
+        self
+    }
+
+    /// 设置 authlib-injector.jar 路径（Yggdrasil 外置登录账号需要），
+    /// 可用 [`ensure_authlib_injector`] 下载
+    pub fn with_authlib_injector(mut self, jar: impl Into<PathBuf>) -> Self {
+        self.authlib_injector = Some(jar.into());
+        self
+    }
+
+    async fn read_version_json(&self, version: &str) -> Result<Vec<u8>> {
+        let path = FileSystemRepository::new(&self.root).get_version_json(version);
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(Error::VersionNotFound(version.to_owned()))
+            }
+            Err(e) => Err(e.into()),
         }
     }
+
+    async fn load_version(&self, version: &str) -> Result<VersionInfo> {
+        let data = self.read_version_json(version).await?;
+        Ok(serde_json::from_slice(&data).map_err(unml_core::JsonError)?)
+    }
+
+    /// 版本 JSON 中 `javaVersion` 声明的 Java 要求
+    pub async fn java_requirement(&self, version: &str) -> Result<JavaRequirement> {
+        let data = self.read_version_json(version).await?;
+        let json: serde_json::Value =
+            serde_json::from_slice(&data).map_err(unml_core::JsonError)?;
+        Ok(JavaRequirement::from_version_json(&json))
+    }
+
+    /// 账号相关的 JVM 参数
+    fn account_jvm_args(&self, account: &Account) -> Result<Vec<String>> {
+        let AccountType::Yggdrasil {
+            api_root, metadata, ..
+        } = &account.account_type
+        else {
+            return Ok(Vec::new());
+        };
+
+        let jar = self
+            .authlib_injector
+            .as_ref()
+            .ok_or(Error::AuthlibInjectorMissing)?;
+
+        let mut args = vec![format!("-javaagent:{}={api_root}", jar.display())];
+        if let Some(metadata) = metadata {
+            args.push(format!("-Dauthlibinjector.yggdrasil.prefetched={metadata}"));
+        }
+
+        Ok(args)
+    }
 }
 
 #[async_trait]
@@ -24,12 +103,31 @@ impl GameLauncher for StandardLauncher {
 
     async fn launch(
         &self,
-        _version: &str,
-        _account: &Account,
-        _config: LaunchConfig,
+        version: &str,
+        account: &Account,
+        config: LaunchConfig,
     ) -> Result<GameProcess> {
-        // TODO: 实现
-        Ok(GameProcess::new(None))
+        let version = self.load_version(version).await?;
+        let game_dir = self.game_dir.as_ref().unwrap_or(&self.root);
+        let arguments = LaunchArguments::new(&version, account, &config, &self.root, game_dir);
+
+        // javaagent 必须在其他 JVM 参数之前
+        let mut command = Command::new(&config.java_path);
+        command
+            .current_dir(game_dir)
+            .args(self.account_jvm_args(account)?)
+            .args(&config.jvm_args)
+            .args(&arguments.jvm)
+            .arg(&version.main_class)
+            .args(&arguments.game)
+            .args(&config.game_args);
+        if let Some(target) = &config.quick_play {
+            command.args(quick_play_args(&version, target)?);
+        }
+
+        natives::extract_natives(&version, &self.root).await?;
+        tokio::fs::create_dir_all(game_dir).await?;
+        GameProcess::spawn(command).map_err(|e| Error::LaunchFailed(e.to_string()))
     }
 }
 
@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use unml_core::{Account, AccountType, GameRepository, LaunchConfig, Library, VersionInfo};
+
+use crate::FileSystemRepository;
+
+/// 1.13 之前的版本 JSON 没有 JVM 参数，使用原版启动器的默认值
+const LEGACY_JVM_ARGS: [&str; 3] = [
+    "-Djava.library.path=${natives_directory}",
+    "-cp",
+    "${classpath}",
+];
+
+const LAUNCHER_NAME: &str = "unml";
+
+/// 设置了窗口大小时启用，对应参数规则中的 `--width`/`--height`
+const CUSTOM_RESOLUTION: &str = "has_custom_resolution";
+
+/// 启动命令中 JVM 参数、主类之后的部分与占位符取值
+pub(crate) struct LaunchArguments {
+    pub jvm: Vec<String>,
+    pub game: Vec<String>,
+}
+
+impl LaunchArguments {
+    /// 展开版本 JSON 中的参数，`root` 为版本、库与资源文件所在目录
+    pub fn new(
+        version: &VersionInfo,
+        account: &Account,
+        config: &LaunchConfig,
+        root: &Path,
+        game_dir: &Path,
+    ) -> Self {
+        let custom_resolution = config.window_width > 0 && config.window_height > 0;
+        let features: &[&str] = if custom_resolution {
+            &[CUSTOM_RESOLUTION]
+        } else {
+            &[]
+        };
+        let values = placeholders(version, account, config, root, game_dir);
+
+        let jvm: Vec<String> = match &version.arguments {
+            Some(arguments) if !arguments.jvm.is_empty() => arguments
+                .jvm
+                .iter()
+                .flat_map(|argument| argument.values(features))
+                .cloned()
+                .collect(),
+            _ => LEGACY_JVM_ARGS.map(str::to_owned).to_vec(),
+        };
+
+        let mut game: Vec<String> = match (&version.arguments, &version.minecraft_arguments) {
+            (Some(arguments), _) if !arguments.game.is_empty() => arguments
+                .game
+                .iter()
+                .flat_map(|argument| argument.values(features))
+                .cloned()
+                .collect(),
+            (_, Some(legacy)) => legacy.split_whitespace().map(str::to_owned).collect(),
+            _ => Vec::new(),
+        };
+        // 旧版本没有参数规则，直接追加
+        if custom_resolution && version.arguments.is_none() {
+            game.extend(
+                [
+                    "--width",
+                    "${resolution_width}",
+                    "--height",
+                    "${resolution_height}",
+                ]
+                .map(str::to_owned),
+            );
+        }
+
+        Self {
+            jvm: jvm.iter().map(|arg| substitute(arg, &values)).collect(),
+            game: game.iter().map(|arg| substitute(arg, &values)).collect(),
+        }
+    }
+}
+
+/// Maven 坐标 `group:artifact:version[:classifier]` 在 `libraries/` 下的路径
+pub(super) fn library_path(name: &str) -> Option<PathBuf> {
+    let mut parts = name.split(':');
+    let (group, artifact, version) = (parts.next()?, parts.next()?, parts.next()?);
+    let file = match parts.next() {
+        Some(classifier) => format!("{artifact}-{version}-{classifier}.jar"),
+        None => format!("{artifact}-{version}.jar"),
+    };
+
+    let mut path: PathBuf = group.split('.').collect();
+    path.push(artifact);
+    path.push(version);
+    path.push(file);
+    Some(path)
+}
+
+/// 当前平台适用的库加上客户端 JAR
+///
+/// 只有原生库分类器的旧版库没有主 JAR，由 [`super::natives`]
+/// 解压而不加入类路径。
+fn classpath(version: &VersionInfo, root: &Path) -> String {
+    let libraries = root.join("libraries");
+    let mut entries: Vec<PathBuf> = Vec::new();
+    let natives_only = |library: &Library| {
+        !library.natives.is_empty()
+            && library
+                .downloads
+                .as_ref()
+                .is_none_or(|downloads| downloads.artifact.is_none())
+    };
+    for library in version
+        .libraries
+        .iter()
+        .filter(|l| l.is_allowed() && !natives_only(l))
+    {
+        if let Some(path) = library_path(&library.name).map(|path| libraries.join(path))
+            && !entries.contains(&path)
+        {
+            entries.push(path);
+        }
+    }
+    entries.push(FileSystemRepository::new(root).get_version_jar(&version.id));
+
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    entries
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn placeholders(
+    version: &VersionInfo,
+    account: &Account,
+    config: &LaunchConfig,
+    root: &Path,
+    game_dir: &Path,
+) -> HashMap<&'static str, String> {
+    let path = |path: &Path| path.to_string_lossy().into_owned();
+    let assets = root.join("assets");
+    let user_type = match account.account_type {
+        AccountType::Microsoft => "msa",
+        AccountType::Yggdrasil { .. } => "mojang",
+        AccountType::Offline => "legacy",
+    };
+
+    HashMap::from([
+        ("auth_player_name", account.username.clone()),
+        ("auth_uuid", account.uuid.clone()),
+        ("auth_access_token", account.access_token.clone()),
+        (
+            "auth_session",
+            format!("token:{}:{}", account.access_token, account.uuid),
+        ),
+        ("auth_xuid", String::new()),
+        ("clientid", String::new()),
+        ("user_type", user_type.to_owned()),
+        ("user_properties", "{}".to_owned()),
+        ("version_name", version.id.clone()),
+        ("version_type", version.type_.clone()),
+        ("game_directory", path(game_dir)),
+        ("assets_root", path(&assets)),
+        ("assets_index_name", version.asset_index.id.clone()),
+        // 1.6 之前的版本从虚拟资源目录读取
+        (
+            "game_assets",
+            path(&assets.join("virtual").join(&version.asset_index.id)),
+        ),
+        (
+            "natives_directory",
+            path(
+                &FileSystemRepository::new(root)
+                    .get_version_path(&version.id)
+                    .join("natives"),
+            ),
+        ),
+        ("library_directory", path(&root.join("libraries"))),
+        (
+            "classpath_separator",
+            if cfg!(windows) { ";" } else { ":" }.to_owned(),
+        ),
+        ("classpath", classpath(version, root)),
+        ("launcher_name", LAUNCHER_NAME.to_owned()),
+        ("launcher_version", env!("CARGO_PKG_VERSION").to_owned()),
+        ("resolution_width", config.window_width.to_string()),
+        ("resolution_height", config.window_height.to_string()),
+    ])
+}
+
+/// 替换参数中的 `${name}` 占位符，未知的占位符保持原样
+fn substitute(arg: &str, values: &HashMap<&'static str, String>) -> String {
+    let mut result = String::with_capacity(arg.len());
+    let mut rest = arg;
+
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + len];
+        result.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use unml_core::QuickPlay;
+
+    use super::*;
+
+    fn account() -> Account {
+        Account {
+            username: "Steve".to_owned(),
+            uuid: "8667ba71b85a4004af54457a9734eed7".to_owned(),
+            access_token: "token".to_owned(),
+            refresh_token: None,
+            account_type: AccountType::Microsoft,
+            local_textures: None,
+        }
+    }
+
+    fn config(width: u32, height: u32) -> LaunchConfig {
+        LaunchConfig {
+            java_path: PathBuf::from("java"),
+            jvm_args: Vec::new(),
+            game_args: Vec::new(),
+            window_width: width,
+            window_height: height,
+            quick_play: None::<QuickPlay>,
+        }
+    }
+
+    fn version(extra: serde_json::Value) -> VersionInfo {
+        let mut json = json!({
+            "id": "1.20.4",
+            "type": "release",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [
+                {"name": "com.mojang:brigadier:1.2.9"},
+                {
+                    "name": "org.lwjgl:lwjgl:3.3.3:natives-windows",
+                    "rules": [{"action": "allow", "os": {"name": "windows"}}]
+                },
+                {
+                    "name": "org.lwjgl:lwjgl:3.3.3:natives-linux",
+                    "rules": [{"action": "allow", "os": {"name": "linux"}}]
+                }
+            ],
+            "assetIndex": {"id": "12", "url": "", "sha1": "", "totalSize": 0}
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn library_paths() {
+        assert_eq!(
+            library_path("com.mojang:brigadier:1.2.9"),
+            Some(PathBuf::from(
+                "com/mojang/brigadier/1.2.9/brigadier-1.2.9.jar"
+            ))
+        );
+        assert_eq!(
+            library_path("org.lwjgl:lwjgl:3.3.3:natives-linux"),
+            Some(PathBuf::from(
+                "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar"
+            ))
+        );
+        assert_eq!(library_path("invalid"), None);
+    }
+
+    #[test]
+    fn placeholders_are_substituted() {
+        let values = HashMap::from([("a", "1".to_owned())]);
+        assert_eq!(substitute("-D${a}=${a}", &values), "-D1=1");
+        assert_eq!(substitute("${unknown}/${a}", &values), "${unknown}/1");
+        assert_eq!(substitute("${a", &values), "${a");
+    }
+
+    #[test]
+    fn modern_arguments() {
+        let version = version(json!({
+            "arguments": {
+                "game": [
+                    "--username", "${auth_player_name}",
+                    "--accessToken", "${auth_access_token}",
+                    {
+                        "rules": [{"action": "allow", "features": {"has_custom_resolution": true}}],
+                        "value": ["--width", "${resolution_width}", "--height", "${resolution_height}"]
+                    },
+                    {
+                        "rules": [{"action": "allow", "features": {"is_quick_play_singleplayer": true}}],
+                        "value": ["--quickPlaySingleplayer", "${quickPlaySingleplayer}"]
+                    }
+                ],
+                "jvm": ["-cp", "${classpath}"]
+            }
+        }));
+        let root = Path::new("root");
+
+        let args = LaunchArguments::new(&version, &account(), &config(0, 0), root, root);
+        assert_eq!(args.game, ["--username", "Steve", "--accessToken", "token"]);
+
+        let args = LaunchArguments::new(&version, &account(), &config(854, 480), root, root);
+        assert_eq!(&args.game[4..], ["--width", "854", "--height", "480"]);
+
+        let classpath: Vec<PathBuf> = std::env::split_paths(&args.jvm[1]).collect();
+        assert_eq!(
+            classpath[0],
+            root.join("libraries/com/mojang/brigadier/1.2.9/brigadier-1.2.9.jar")
+        );
+        // 只保留当前平台的 natives
+        let natives: Vec<_> = classpath
+            .iter()
+            .filter(|path| path.to_string_lossy().contains("natives-"))
+            .collect();
+        assert_eq!(
+            natives.len(),
+            usize::from(cfg!(any(windows, target_os = "linux")))
+        );
+        assert_eq!(
+            classpath.last(),
+            Some(&root.join("versions/1.20.4/1.20.4.jar"))
+        );
+    }
+
+    #[test]
+    fn legacy_arguments() {
+        let version = version(json!({
+            "libraries": [
+                {"name": "com.mojang:netty:1.6"},
+                {
+                    "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+                    "natives": {"linux": "natives-linux", "osx": "natives-osx", "windows": "natives-windows"}
+                }
+            ],
+            "minecraftArguments": "--username ${auth_player_name} --session ${auth_session} --gameDir ${game_directory}"
+        }));
+        let root = Path::new("root");
+
+        let args = LaunchArguments::new(
+            &version,
+            &account(),
+            &config(854, 480),
+            root,
+            Path::new("game"),
+        );
+        assert_eq!(args.jvm[1], "-cp");
+        // 只有原生库的库由启动前的解压处理，不加入类路径
+        let classpath: Vec<PathBuf> = std::env::split_paths(&args.jvm[2]).collect();
+        assert_eq!(
+            classpath,
+            [
+                root.join("libraries/com/mojang/netty/1.6/netty-1.6.jar"),
+                root.join("versions/1.20.4/1.20.4.jar"),
+            ]
+        );
+        assert_eq!(
+            args.game,
+            [
+                "--username",
+                "Steve",
+                "--session",
+                "token:token:8667ba71b85a4004af54457a9734eed7",
+                "--gameDir",
+                "game",
+                "--width",
+                "854",
+                "--height",
+                "480",
+            ]
+        );
+    }
+}
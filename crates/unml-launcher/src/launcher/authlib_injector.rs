@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use unml_core::{Checksum, DownloadProvider};
+
+use crate::Result;
+
+/// authlib-injector 最新版本的元数据
+const LATEST_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+const JAR_FILE: &str = "authlib-injector.jar";
+/// 下载元数据时的临时文件，读取后删除
+const LATEST_FILE: &str = ".authlib-injector.json";
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    download_url: String,
+    checksums: Checksums,
+}
+
+#[derive(Debug, Deserialize)]
+struct Checksums {
+    sha256: String,
+}
+
+/// `root` 中 authlib-injector.jar 的路径，不存在时下载最新版本
+///
+/// 下载的 JAR 按元数据中的 SHA-256 校验，校验失败时不会留下文件。
+pub async fn ensure_authlib_injector<P>(provider: &P, root: &Path) -> Result<PathBuf>
+where
+    P: DownloadProvider<Error = unml_download::Error>,
+{
+    let jar = root.join(JAR_FILE);
+    if tokio::fs::try_exists(&jar).await? {
+        return Ok(jar);
+    }
+
+    let latest = root.join(LATEST_FILE);
+    provider
+        .download_file(LATEST_URL, &latest, None, None)
+        .await?;
+    let data = tokio::fs::read(&latest).await;
+    let _ = tokio::fs::remove_file(&latest).await;
+    let artifact: Artifact = serde_json::from_slice(&data?).map_err(unml_core::JsonError)?;
+
+    provider
+        .download_file(
+            &artifact.download_url,
+            &jar,
+            Some(&Checksum::Sha256(artifact.checksums.sha256)),
+            None,
+        )
+        .await?;
+
+    Ok(jar)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use unml_core::{ProgressCallback, VersionInfo, VersionManifest};
+
+    use super::*;
+
+    /// 只在测试中使用的下载器，所有请求都失败
+    struct Offline;
+
+    #[async_trait]
+    impl DownloadProvider for Offline {
+        type Error = unml_download::Error;
+
+        async fn fetch_version_manifest(&self) -> unml_download::Result<VersionManifest> {
+            unreachable!()
+        }
+
+        async fn fetch_version_info(&self, _: &str) -> unml_download::Result<VersionInfo> {
+            unreachable!()
+        }
+
+        async fn download_file(
+            &self,
+            url: &str,
+            _: &Path,
+            _: Option<&Checksum>,
+            _: Option<ProgressCallback>,
+        ) -> unml_download::Result<()> {
+            Err(unml_core::HttpError(format!("offline: {url}")).into())
+        }
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "unml-authlib-injector-{}-{name}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn existing_jar_is_reused() {
+        let root = temp_root("existing");
+        std::fs::write(root.join(JAR_FILE), b"jar").unwrap();
+
+        let jar = ensure_authlib_injector(&Offline, &root).await.unwrap();
+        assert_eq!(jar, root.join(JAR_FILE));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn download_failure_leaves_no_jar() {
+        let root = temp_root("offline");
+
+        assert!(ensure_authlib_injector(&Offline, &root).await.is_err());
+        assert!(!root.join(JAR_FILE).exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parses_latest_metadata() {
+        let artifact: Artifact = serde_json::from_str(
+            r#"{
+                "build_number": 53,
+                "version": "1.2.5",
+                "download_url": "https://authlib-injector.yushi.moe/artifact/53/authlib-injector-1.2.5.jar",
+                "checksums": {"sha256": "abc123"}
+            }"#,
+        )
+        .unwrap();
+        assert!(
+            artifact
+                .download_url
+                .ends_with("authlib-injector-1.2.5.jar")
+        );
+        assert_eq!(artifact.checksums.sha256, "abc123");
+    }
+}
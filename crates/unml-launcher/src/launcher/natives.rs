@@ -0,0 +1,164 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use unml_core::{GameRepository, VersionInfo};
+
+use super::arguments::library_path;
+use crate::{Error, FileSystemRepository, Result};
+
+/// 原生库 JAR 中始终跳过的签名目录
+const META_INF: &str = "META-INF/";
+
+/// 把当前平台的原生库解压到 `${natives_directory}`（`versions/<id>/natives`）
+///
+/// 已存在且大小相同的文件不会重复写入，游戏运行中被占用的 DLL 也因此不受影响。
+pub(super) async fn extract_natives(version: &VersionInfo, root: &Path) -> Result<()> {
+    let libraries = root.join("libraries");
+    let mut jars: Vec<(PathBuf, Vec<String>)> = Vec::new();
+    for library in version.libraries.iter().filter(|l| l.is_allowed()) {
+        let Some(classifier) = library.native_classifier() else {
+            continue;
+        };
+        let name = format!("{}:{classifier}", library.name);
+        let jar = library_path(&name)
+            .map(|path| libraries.join(path))
+            .ok_or_else(|| Error::LibraryNotFound(name.clone()))?;
+        if !tokio::fs::try_exists(&jar).await? {
+            return Err(Error::LibraryNotFound(name));
+        }
+
+        let exclude = library
+            .extract
+            .as_ref()
+            .map(|extract| extract.exclude.clone())
+            .unwrap_or_default();
+        jars.push((jar, exclude));
+    }
+    if jars.is_empty() {
+        return Ok(());
+    }
+
+    let dest = FileSystemRepository::new(root)
+        .get_version_path(&version.id)
+        .join("natives");
+    tokio::task::spawn_blocking(move || {
+        jars.iter()
+            .try_for_each(|(jar, exclude)| extract_jar(jar, exclude, &dest))
+    })
+    .await
+    .map_err(|e| Error::NativesExtract(e.to_string()))?
+}
+
+fn extract_jar(jar: &Path, exclude: &[String], dest: &Path) -> Result<()> {
+    let invalid =
+        |e: zip::result::ZipError| Error::NativesExtract(format!("{}: {e}", jar.display()));
+
+    let mut zip = zip::ZipArchive::new(File::open(jar)?).map_err(invalid)?;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(invalid)?;
+        let skipped = entry.is_dir()
+            || entry.name().starts_with(META_INF)
+            || exclude
+                .iter()
+                .any(|prefix| entry.name().starts_with(prefix.as_str()));
+        let Some(relative) = entry.enclosed_name().filter(|_| !skipped) else {
+            continue;
+        };
+
+        let target = dest.join(relative);
+        if std::fs::metadata(&target).is_ok_and(|meta| meta.len() == entry.size()) {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut entry, &mut File::create(&target)?)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use serde_json::json;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("unml-natives-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    /// 带有 LWJGL 2 风格原生库的版本，各平台分类器都指向同一个 JAR
+    fn version() -> VersionInfo {
+        serde_json::from_value(json!({
+            "id": "1.8.9",
+            "type": "release",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [{
+                "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+                "natives": {"linux": "natives", "osx": "natives", "windows": "natives"},
+                "extract": {"exclude": ["META-INF/", "excluded/"]}
+            }],
+            "assetIndex": {"id": "1.8", "url": "", "sha1": "", "totalSize": 0}
+        }))
+        .unwrap()
+    }
+
+    fn write_jar(root: &Path, files: &[(&str, &str)]) {
+        let path = root
+            .join("libraries")
+            .join(library_path("org.lwjgl.lwjgl:lwjgl-platform:2.9.4:natives").unwrap());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn extracts_classifier_jars() {
+        let root = temp_root("extract");
+        write_jar(
+            &root,
+            &[
+                ("liblwjgl.so", "native"),
+                ("META-INF/MANIFEST.MF", "manifest"),
+                ("excluded/file", "excluded"),
+            ],
+        );
+
+        extract_natives(&version(), &root).await.unwrap();
+
+        let natives = root.join("versions/1.8.9/natives");
+        assert_eq!(
+            std::fs::read_to_string(natives.join("liblwjgl.so")).unwrap(),
+            "native"
+        );
+        assert!(!natives.join("META-INF").exists());
+        assert!(!natives.join("excluded").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn missing_jar_is_an_error() {
+        let root = temp_root("missing");
+
+        let err = extract_natives(&version(), &root).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LibraryNotFound(name) if name == "org.lwjgl.lwjgl:lwjgl-platform:2.9.4:natives"
+        ));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
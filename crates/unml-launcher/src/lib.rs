@@ -1,9 +1,37 @@
 mod error;
+mod instance;
+mod jvm;
 mod launcher;
+mod multiplayer;
+mod nbt;
+mod quick_play;
 mod repository;
+mod server;
+mod world;
 
 pub use error::{Error, Result};
-pub use launcher::StandardLauncher;
+pub use instance::{
+    ClientOnlyMod, ClientOnlySource, ConflictKind, DEFAULT_EXPORT_INCLUDE,
+    DEFAULT_SERVER_PACK_INCLUDE, ExportOptions, ExportReport, ImportReport, InstanceManager,
+    ServerPackOptions, ServerPackReport, UpdateConflict, UpdateReport,
+};
+pub use jvm::{
+    JvmArgIssue, JvmPreset, MemoryInfo, count_mods, merge_jvm_args, recommended_heap,
+    validate_jvm_args,
+};
+pub use launcher::{StandardLauncher, ensure_authlib_injector};
+pub use multiplayer::{
+    PlayerSample, SERVERS_FILE, ServerEntry, ServerList, ServerStatus, parse_address, ping,
+    ping_legacy, ping_modern,
+};
+pub use nbt::{
+    NbtCompression, Tag, read_nbt, read_nbt_compressed, write_nbt, write_nbt_compressed,
+};
+pub use quick_play::quick_play_args;
 pub use repository::FileSystemRepository;
+pub use server::{
+    DedicatedServer, Difficulty, GameMode, ServerInstall, ServerLaunch, ServerProperties,
+};
 // 重新导出 unml-java
-pub use unml_java::{JavaDetector, JavaInstallation, JavaManager, JavaVersion};
+pub use unml_java::{JavaDetector, JavaInstallation, JavaManager, JavaRequirement, JavaVersion};
+pub use world::{SAVES_DIR, WorldInfo, read_world, scan_worlds};
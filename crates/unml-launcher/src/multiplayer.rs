@@ -0,0 +1,175 @@
+mod ping;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub use ping::{PlayerSample, ServerStatus, parse_address, ping, ping_legacy, ping_modern};
+
+use crate::nbt::{Tag, read_nbt, write_nbt};
+use crate::{Error, Result};
+
+/// 游戏目录下保存多人游戏服务器列表的文件
+pub const SERVERS_FILE: &str = "servers.dat";
+
+/// `servers.dat` 中的一个服务器
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerEntry {
+    pub name: String,
+    /// 服务器地址，可带端口
+    pub ip: String,
+    /// Base64 编码的 PNG 图标，不含 `data:` 前缀
+    pub icon: Option<String>,
+    /// 是否接受服务器资源包，`None` 表示每次询问
+    pub accept_textures: Option<bool>,
+    /// 由游戏内 `/connect` 等方式加入、未显示在列表中的服务器
+    pub hidden: bool,
+    /// 未识别的字段，写回时保留
+    pub extra: BTreeMap<String, Tag>,
+}
+
+impl ServerEntry {
+    pub fn new(name: impl Into<String>, ip: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ip: ip.into(),
+            icon: None,
+            accept_textures: None,
+            hidden: false,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    fn from_tag(tag: &Tag) -> Result<Self> {
+        let mut map = tag
+            .as_compound()
+            .ok_or_else(|| Error::InvalidNbt("server entry is not a compound".into()))?
+            .clone();
+        let mut string = |key: &str| match map.remove(key) {
+            Some(Tag::String(s)) => Some(s),
+            _ => None,
+        };
+        let name = string("name").unwrap_or_default();
+        let ip = string("ip").unwrap_or_default();
+        let icon = string("icon");
+        Ok(Self {
+            name,
+            ip,
+            icon,
+            accept_textures: map.remove("acceptTextures").and_then(|t| t.as_bool()),
+            hidden: map
+                .remove("hidden")
+                .and_then(|t| t.as_bool())
+                .unwrap_or(false),
+            extra: map,
+        })
+    }
+
+    fn to_tag(&self) -> Tag {
+        let mut map = self.extra.clone();
+        map.insert("name".into(), Tag::String(self.name.clone()));
+        map.insert("ip".into(), Tag::String(self.ip.clone()));
+        if let Some(icon) = &self.icon {
+            map.insert("icon".into(), Tag::String(icon.clone()));
+        }
+        if let Some(accept) = self.accept_textures {
+            map.insert("acceptTextures".into(), Tag::Byte(accept.into()));
+        }
+        if self.hidden {
+            map.insert("hidden".into(), Tag::Byte(1));
+        }
+        Tag::Compound(map)
+    }
+}
+
+/// 未压缩 NBT 格式的 `servers.dat`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerList {
+    pub servers: Vec<ServerEntry>,
+}
+
+impl ServerList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 读取服务器列表，文件不存在时返回空列表
+    pub async fn load(path: &Path) -> Result<Self> {
+        match tokio::fs::read(path).await {
+            Ok(data) => Self::parse(&data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, self.to_bytes()?).await?;
+        Ok(())
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, root) = read_nbt(data)?;
+        let servers = match root.as_compound().and_then(|root| root.get("servers")) {
+            Some(tag) => tag
+                .as_list()
+                .ok_or_else(|| Error::InvalidNbt("servers is not a list".into()))?
+                .iter()
+                .map(ServerEntry::from_tag)
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+        Ok(Self { servers })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let servers = self.servers.iter().map(ServerEntry::to_tag).collect();
+        let root = BTreeMap::from([("servers".to_owned(), Tag::List(servers))]);
+        write_nbt("", &Tag::Compound(root))
+    }
+
+    /// 游戏内列表显示的服务器
+    pub fn visible(&self) -> impl Iterator<Item = &ServerEntry> {
+        self.servers.iter().filter(|s| !s.hidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_preserves_extra_fields() {
+        let mut entry = ServerEntry::new("测试服务器 𝄞", "mc.example.com:25566");
+        entry.icon = Some("iVBORw0KGgo=".to_owned());
+        entry.accept_textures = Some(false);
+        entry
+            .extra
+            .insert("preventsChatReports".to_owned(), Tag::Byte(1));
+        let mut hidden = ServerEntry::new("Hidden", "127.0.0.1");
+        hidden.hidden = true;
+        let list = ServerList {
+            servers: vec![entry, hidden],
+        };
+
+        let parsed = ServerList::parse(&list.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, list);
+        assert_eq!(
+            parsed.servers[0].extra.get("preventsChatReports"),
+            Some(&Tag::Byte(1))
+        );
+        assert_eq!(parsed.visible().count(), 1);
+    }
+
+    #[test]
+    fn oversized_icon_is_rejected() {
+        let mut entry = ServerEntry::new("Server", "example.com");
+        entry.icon = Some("A".repeat(70_000));
+        let list = ServerList {
+            servers: vec![entry],
+        };
+
+        assert!(matches!(list.to_bytes(), Err(Error::InvalidNbt(_))));
+    }
+}
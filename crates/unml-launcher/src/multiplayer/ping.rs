@@ -0,0 +1,487 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::{Error, Result};
+
+const DEFAULT_PORT: u16 = 25565;
+/// 状态响应的长度上限，带图标的响应通常只有几十 KB
+const MAX_PACKET_LEN: usize = 2 * 1024 * 1024;
+/// 握手时声明的协议版本，-1 表示客户端不确定自己的版本
+const PING_PROTOCOL: i32 = -1;
+/// 等待 Pong 的时间，超时不影响已经取得的状态
+const PONG_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 服务器列表中展示的在线玩家
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PlayerSample {
+    pub name: String,
+    #[serde(default)]
+    pub id: String,
+}
+
+/// 服务器列表 Ping 的结果
+#[derive(Debug, Clone)]
+pub struct ServerStatus {
+    /// 纯文本 MOTD，保留 `§` 格式代码
+    pub motd: String,
+    pub version: String,
+    /// 旧版 Beta 1.8–1.3 服务器不返回协议号
+    pub protocol: Option<i32>,
+    pub online: u32,
+    pub max: u32,
+    pub sample: Vec<PlayerSample>,
+    /// Base64 编码的 PNG 图标，不含 `data:` 前缀，可直接写入 `servers.dat`
+    pub favicon: Option<String>,
+    pub latency: Duration,
+}
+
+/// 解析 `host[:port]`，IPv6 地址需写成 `[::1]:25565`
+///
+/// 不查询 SRV 记录。
+pub fn parse_address(address: &str) -> (String, u16) {
+    let address = address.trim();
+    if let Some(rest) = address.strip_prefix('[')
+        && let Some((host, tail)) = rest.split_once(']')
+    {
+        let port = tail.strip_prefix(':').and_then(|p| p.parse().ok());
+        return (host.to_owned(), port.unwrap_or(DEFAULT_PORT));
+    }
+    match address.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => match port.parse() {
+            Ok(port) => (host.to_owned(), port),
+            Err(_) => (address.to_owned(), DEFAULT_PORT),
+        },
+        _ => (address.to_owned(), DEFAULT_PORT),
+    }
+}
+
+/// Ping 服务器，先使用 1.7+ 的状态协议，失败后回退到旧版 `0xFE` Ping
+pub async fn ping(address: &str, timeout: Duration) -> Result<ServerStatus> {
+    let (host, port) = parse_address(address);
+    match ping_modern(&host, port, timeout).await {
+        Ok(status) => Ok(status),
+        Err(modern) => ping_legacy(&host, port, timeout).await.map_err(|_| modern),
+    }
+}
+
+/// 1.7+ 的握手与状态请求
+pub async fn ping_modern(host: &str, port: u16, timeout: Duration) -> Result<ServerStatus> {
+    let (mut stream, mut status) = with_timeout(timeout, async {
+        let mut stream = TcpStream::connect((host, port)).await?;
+        stream.set_nodelay(true)?;
+
+        let mut handshake = Vec::new();
+        write_varint(&mut handshake, 0x00);
+        write_varint(&mut handshake, PING_PROTOCOL);
+        write_varint(&mut handshake, host.len() as i32);
+        handshake.extend_from_slice(host.as_bytes());
+        handshake.extend_from_slice(&port.to_be_bytes());
+        write_varint(&mut handshake, 1);
+        send_packet(&mut stream, &handshake).await?;
+
+        let started = Instant::now();
+        send_packet(&mut stream, &[0x00]).await?;
+        let packet = read_packet(&mut stream).await?;
+        let latency = started.elapsed();
+        let mut body = packet.as_slice();
+        if read_varint_slice(&mut body)? != 0x00 {
+            return Err(ping_error("unexpected status response"));
+        }
+        let len = read_varint_slice(&mut body)?.max(0) as usize;
+        let json = body
+            .get(..len)
+            .ok_or_else(|| ping_error("truncated status response"))?;
+        let mut status = parse_status(json)?;
+        status.latency = latency;
+        Ok((stream, status))
+    })
+    .await?;
+
+    // 部分服务器不响应 Ping 包或直接断开，此时保留状态请求的耗时作为延迟
+    let payload = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64);
+    let mut ping = vec![0x01];
+    ping.extend_from_slice(&payload.to_be_bytes());
+    let started = Instant::now();
+    let pong = tokio::time::timeout(PONG_TIMEOUT.min(timeout), async {
+        send_packet(&mut stream, &ping).await?;
+        read_packet(&mut stream).await
+    })
+    .await;
+    if let Ok(Ok(pong)) = pong
+        && pong.first() == Some(&0x01)
+    {
+        status.latency = started.elapsed();
+    }
+
+    Ok(status)
+}
+
+/// Beta 1.8 至 1.6 的 `0xFE 0x01` Ping
+pub async fn ping_legacy(host: &str, port: u16, timeout: Duration) -> Result<ServerStatus> {
+    with_timeout(timeout, async {
+        let mut stream = TcpStream::connect((host, port)).await?;
+        let started = Instant::now();
+        stream.write_all(&[0xFE, 0x01]).await?;
+        if stream.read_u8().await? != 0xFF {
+            return Err(ping_error("unexpected legacy ping response"));
+        }
+        let len = stream.read_u16().await? as usize;
+        let mut bytes = vec![0; len * 2];
+        stream.read_exact(&mut bytes).await?;
+        let latency = started.elapsed();
+        let units: Vec<u16> = bytes
+            .as_chunks::<2>()
+            .0
+            .iter()
+            .map(|b| u16::from_be_bytes(*b))
+            .collect();
+        let text = String::from_utf16_lossy(&units);
+        parse_legacy(&text, latency)
+    })
+    .await
+}
+
+async fn with_timeout<T>(timeout: Duration, future: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| ping_error("timed out"))?
+}
+
+fn ping_error(msg: &str) -> Error {
+    Error::Ping(msg.to_owned())
+}
+
+fn write_varint(out: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            out.push(value as u8);
+            return;
+        }
+        out.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+fn read_varint_slice(data: &mut &[u8]) -> Result<i32> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let (&byte, rest) = data
+            .split_first()
+            .ok_or_else(|| ping_error("truncated VarInt"))?;
+        *data = rest;
+        value |= ((byte & 0x7F) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(ping_error("VarInt too long"))
+}
+
+async fn read_varint(stream: &mut TcpStream) -> Result<i32> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let byte = stream.read_u8().await?;
+        value |= ((byte & 0x7F) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(ping_error("VarInt too long"))
+}
+
+async fn send_packet(stream: &mut TcpStream, packet: &[u8]) -> Result<()> {
+    let mut out = Vec::with_capacity(packet.len() + 5);
+    write_varint(&mut out, packet.len() as i32);
+    out.extend_from_slice(packet);
+    stream.write_all(&out).await?;
+    Ok(())
+}
+
+async fn read_packet(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let len = read_varint(stream).await?;
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|&len| len > 0 && len <= MAX_PACKET_LEN)
+        .ok_or_else(|| ping_error("invalid packet length"))?;
+    let mut packet = vec![0; len];
+    stream.read_exact(&mut packet).await?;
+    Ok(packet)
+}
+
+#[derive(Deserialize)]
+struct StatusResponse {
+    #[serde(default)]
+    version: Option<StatusVersion>,
+    #[serde(default)]
+    players: Option<StatusPlayers>,
+    #[serde(default)]
+    description: Value,
+    #[serde(default)]
+    favicon: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StatusVersion {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    protocol: i32,
+}
+
+#[derive(Deserialize)]
+struct StatusPlayers {
+    #[serde(default)]
+    max: u32,
+    #[serde(default)]
+    online: u32,
+    #[serde(default)]
+    sample: Vec<PlayerSample>,
+}
+
+fn parse_status(json: &[u8]) -> Result<ServerStatus> {
+    let response: StatusResponse = serde_json::from_slice(json).map_err(unml_core::JsonError)?;
+    let players = response.players;
+    let mut motd = String::new();
+    flatten_text(&response.description, &mut motd);
+    Ok(ServerStatus {
+        motd,
+        version: response
+            .version
+            .as_ref()
+            .map(|v| v.name.clone())
+            .unwrap_or_default(),
+        protocol: response.version.map(|v| v.protocol),
+        online: players.as_ref().map_or(0, |p| p.online),
+        max: players.as_ref().map_or(0, |p| p.max),
+        sample: players.map(|p| p.sample).unwrap_or_default(),
+        favicon: response.favicon.map(|f| match f.split_once(',') {
+            Some((_, data)) => data.to_owned(),
+            None => f,
+        }),
+        latency: Duration::ZERO,
+    })
+}
+
+/// 将聊天组件展开为纯文本
+fn flatten_text(value: &Value, out: &mut String) {
+    match value {
+        Value::String(s) => out.push_str(s),
+        Value::Array(parts) => parts.iter().for_each(|part| flatten_text(part, out)),
+        Value::Object(map) => {
+            if let Some(Value::String(text)) = map.get("text") {
+                out.push_str(text);
+            } else if let Some(Value::String(key)) = map.get("translate") {
+                out.push_str(key);
+            }
+            if let Some(extra) = map.get("extra") {
+                flatten_text(extra, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 1.4+ 返回 `§1\0协议\0版本\0MOTD\0在线\0上限`，更早的版本返回
+/// `MOTD§在线§上限`
+fn parse_legacy(text: &str, latency: Duration) -> Result<ServerStatus> {
+    if let Some(rest) = text.strip_prefix("\u{a7}1\0") {
+        let fields: Vec<&str> = rest.split('\0').collect();
+        let [protocol, version, motd, online, max] = fields[..] else {
+            return Err(ping_error("malformed legacy ping response"));
+        };
+        return Ok(ServerStatus {
+            motd: motd.to_owned(),
+            version: version.to_owned(),
+            protocol: protocol.parse().ok(),
+            online: online.parse().unwrap_or(0),
+            max: max.parse().unwrap_or(0),
+            sample: Vec::new(),
+            favicon: None,
+            latency,
+        });
+    }
+    let mut fields = text.rsplitn(3, '\u{a7}');
+    let (Some(max), Some(online), Some(motd)) = (fields.next(), fields.next(), fields.next())
+    else {
+        return Err(ping_error("malformed legacy ping response"));
+    };
+    Ok(ServerStatus {
+        motd: motd.to_owned(),
+        version: String::new(),
+        protocol: None,
+        online: online.parse().unwrap_or(0),
+        max: max.parse().unwrap_or(0),
+        sample: Vec::new(),
+        favicon: None,
+        latency,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    const STATUS_JSON: &str = r#"{
+        "version": {"name": "1.21.1", "protocol": 767},
+        "players": {"max": 20, "online": 2, "sample": [{"name": "Steve", "id": "8667ba71-b85a-4004-af54-457a9734eed7"}]},
+        "description": {"text": "Hello ", "extra": [{"text": "§aWorld"}]},
+        "favicon": "data:image/png;base64,iVBORw0KGgo="
+    }"#;
+
+    /// 在本地端口上运行一次性的假服务器
+    async fn fake_server<F, Fut>(handler: F) -> u16
+    where
+        F: FnOnce(TcpStream) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            handler(stream).await;
+        });
+        port
+    }
+
+    /// 读取握手与状态请求并返回状态响应
+    async fn answer_status(stream: &mut TcpStream, json: &str) {
+        let handshake = read_packet(stream).await.unwrap();
+        let mut body = handshake.as_slice();
+        assert_eq!(read_varint_slice(&mut body).unwrap(), 0x00);
+        assert_eq!(read_varint_slice(&mut body).unwrap(), PING_PROTOCOL);
+        assert_eq!(*body.last().unwrap(), 1, "next state should be status");
+
+        assert_eq!(read_packet(stream).await.unwrap(), [0x00]);
+
+        let mut response = Vec::new();
+        write_varint(&mut response, 0x00);
+        write_varint(&mut response, json.len() as i32);
+        response.extend_from_slice(json.as_bytes());
+        send_packet(stream, &response).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn modern_status_and_pong() {
+        let port = fake_server(|mut stream| async move {
+            answer_status(&mut stream, STATUS_JSON).await;
+            let ping = read_packet(&mut stream).await.unwrap();
+            assert_eq!(ping[0], 0x01);
+            assert_eq!(ping.len(), 9);
+            send_packet(&mut stream, &ping).await.unwrap();
+        })
+        .await;
+
+        let status = ping_modern("127.0.0.1", port, TIMEOUT).await.unwrap();
+        assert_eq!(status.motd, "Hello §aWorld");
+        assert_eq!(status.version, "1.21.1");
+        assert_eq!(status.protocol, Some(767));
+        assert_eq!((status.online, status.max), (2, 20));
+        assert_eq!(status.sample.len(), 1);
+        assert_eq!(status.sample[0].name, "Steve");
+        assert_eq!(status.favicon.as_deref(), Some("iVBORw0KGgo="));
+    }
+
+    #[tokio::test]
+    async fn missing_pong_keeps_status() {
+        let port = fake_server(|mut stream| async move {
+            answer_status(&mut stream, r#"{"description": "No pong"}"#).await;
+            // 收到 Ping 后不响应，保持连接直到客户端放弃
+            let _ = read_packet(&mut stream).await;
+            tokio::time::sleep(TIMEOUT).await;
+        })
+        .await;
+
+        let started = Instant::now();
+        let status = ping_modern("127.0.0.1", port, TIMEOUT).await.unwrap();
+        assert_eq!(status.motd, "No pong");
+        assert!(started.elapsed() < TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn legacy_ping() {
+        let port = fake_server(|mut stream| async move {
+            let mut request = [0; 2];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request, [0xFE, 0x01]);
+
+            let text: Vec<u16> = "§1\u{0}78\u{0}1.6.4\u{0}Old MOTD\u{0}3\u{0}10"
+                .encode_utf16()
+                .collect();
+            let mut response = vec![0xFF];
+            response.extend_from_slice(&(text.len() as u16).to_be_bytes());
+            for unit in text {
+                response.extend_from_slice(&unit.to_be_bytes());
+            }
+            stream.write_all(&response).await.unwrap();
+        })
+        .await;
+
+        let status = ping_legacy("127.0.0.1", port, TIMEOUT).await.unwrap();
+        assert_eq!(status.motd, "Old MOTD");
+        assert_eq!(status.version, "1.6.4");
+        assert_eq!(status.protocol, Some(78));
+        assert_eq!((status.online, status.max), (3, 10));
+    }
+
+    #[test]
+    fn beta_legacy_response() {
+        let status = parse_legacy("A §server§5§20", Duration::ZERO).unwrap();
+        assert_eq!(status.motd, "A §server");
+        assert_eq!(status.protocol, None);
+        assert_eq!((status.online, status.max), (5, 20));
+    }
+
+    #[tokio::test]
+    async fn truncated_packet() {
+        let port = fake_server(|mut stream| async move {
+            let _ = read_packet(&mut stream).await;
+            let _ = read_packet(&mut stream).await;
+            // 声明 100 字节但只发送 10 字节后断开
+            let mut response = Vec::new();
+            write_varint(&mut response, 100);
+            response.extend_from_slice(&[0; 10]);
+            stream.write_all(&response).await.unwrap();
+        })
+        .await;
+
+        assert!(ping_modern("127.0.0.1", port, TIMEOUT).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn oversize_packet() {
+        let port = fake_server(|mut stream| async move {
+            let _ = read_packet(&mut stream).await;
+            let _ = read_packet(&mut stream).await;
+            let mut response = Vec::new();
+            write_varint(&mut response, MAX_PACKET_LEN as i32 + 1);
+            stream.write_all(&response).await.unwrap();
+            tokio::time::sleep(TIMEOUT).await;
+        })
+        .await;
+
+        let error = ping_modern("127.0.0.1", port, TIMEOUT).await.unwrap_err();
+        assert!(matches!(error, Error::Ping(msg) if msg == "invalid packet length"));
+    }
+
+    #[test]
+    fn addresses() {
+        assert_eq!(parse_address("example.com"), ("example.com".into(), 25565));
+        assert_eq!(
+            parse_address("example.com:25566"),
+            ("example.com".into(), 25566)
+        );
+        assert_eq!(parse_address("[::1]:25566"), ("::1".into(), 25566));
+        assert_eq!(parse_address("[::1]"), ("::1".into(), 25565));
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+
+use crate::{Error, Result};
+
+/// 嵌套层数上限，避免恶意文件导致栈溢出
+const MAX_DEPTH: usize = 512;
+/// 解压后的大小上限，避免压缩炸弹
+const MAX_DECOMPRESSED: u64 = 64 * 1024 * 1024;
+
+/// NBT 文件的压缩方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NbtCompression {
+    /// `servers.dat` 等
+    None,
+    /// `level.dat`、`playerdata/*.dat` 等
+    Gzip,
+    /// 区块数据
+    Zlib,
+}
+
+impl NbtCompression {
+    /// 根据文件头判断压缩方式
+    pub fn detect(data: &[u8]) -> Self {
+        match data {
+            [0x1F, 0x8B, ..] => Self::Gzip,
+            [0x78, b, ..] if (0x7800 | *b as u16) % 31 == 0 => Self::Zlib,
+            _ => Self::None,
+        }
+    }
+}
+
+/// NBT 标签（Java 版，大端序）
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    /// 空列表写出时元素类型为 `TAG_End`
+    List(Vec<Tag>),
+    Compound(BTreeMap<String, Tag>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::Byte(_) => 1,
+            Self::Short(_) => 2,
+            Self::Int(_) => 3,
+            Self::Long(_) => 4,
+            Self::Float(_) => 5,
+            Self::Double(_) => 6,
+            Self::ByteArray(_) => 7,
+            Self::String(_) => 8,
+            Self::List(_) => 9,
+            Self::Compound(_) => 10,
+            Self::IntArray(_) => 11,
+            Self::LongArray(_) => 12,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// 整数类标签的值
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Byte(v) => Some(v.into()),
+            Self::Short(v) => Some(v.into()),
+            Self::Int(v) => Some(v.into()),
+            Self::Long(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// 布尔值以 `TAG_Byte` 存储
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_i64().map(|v| v != 0)
+    }
+
+    pub fn as_list(&self) -> Option<&[Tag]> {
+        match self {
+            Self::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&BTreeMap<String, Tag>> {
+        match self {
+            Self::Compound(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+/// 读取未压缩的 NBT，返回根标签的名称和值
+pub fn read_nbt(data: &[u8]) -> Result<(String, Tag)> {
+    let mut reader = Reader { data, pos: 0 };
+    let id = reader.u8()?;
+    if id != 10 {
+        return Err(invalid(format!("root tag must be a compound, found {id}")));
+    }
+    let name = reader.string()?;
+    let tag = reader.payload(id, 0)?;
+    Ok((name, tag))
+}
+
+/// 写出未压缩的 NBT
+///
+/// 超长字符串或元素类型不一致的列表会返回错误，而不是写出损坏的数据。
+pub fn write_nbt(name: &str, tag: &Tag) -> Result<Vec<u8>> {
+    let mut out = vec![tag.id()];
+    write_string(&mut out, name)?;
+    write_payload(&mut out, tag)?;
+    Ok(out)
+}
+
+/// 读取 NBT，自动识别 gzip/zlib 压缩
+pub fn read_nbt_compressed(data: &[u8]) -> Result<(String, Tag, NbtCompression)> {
+    let compression = NbtCompression::detect(data);
+    let (name, tag) = match compression {
+        NbtCompression::None => read_nbt(data)?,
+        NbtCompression::Gzip => read_nbt(&inflate(GzDecoder::new(data))?)?,
+        NbtCompression::Zlib => read_nbt(&inflate(ZlibDecoder::new(data))?)?,
+    };
+    Ok((name, tag, compression))
+}
+
+/// 按指定方式压缩写出 NBT
+pub fn write_nbt_compressed(name: &str, tag: &Tag, compression: NbtCompression) -> Result<Vec<u8>> {
+    let data = write_nbt(name, tag)?;
+    let level = flate2::Compression::default();
+    Ok(match compression {
+        NbtCompression::None => data,
+        NbtCompression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), level);
+            encoder.write_all(&data)?;
+            encoder.finish()?
+        }
+        NbtCompression::Zlib => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), level);
+            encoder.write_all(&data)?;
+            encoder.finish()?
+        }
+    })
+}
+
+fn inflate(decoder: impl Read) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| invalid(format!("decompression failed: {e}")))?;
+    if data.len() as u64 > MAX_DECOMPRESSED {
+        return Err(invalid("decompressed data too large"));
+    }
+    Ok(data)
+}
+
+fn invalid(msg: impl Into<String>) -> Error {
+    Error::InvalidNbt(msg.into())
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("length checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+
+    /// 数组与列表的长度，负数视为空
+    fn len(&mut self) -> Result<usize> {
+        Ok(self.i32()?.max(0) as usize)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = u16::from_be_bytes(self.array()?) as usize;
+        decode_mutf8(self.take(len)?)
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> Result<Tag> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("nesting too deep"));
+        }
+        Ok(match id {
+            1 => Tag::Byte(self.u8()? as i8),
+            2 => Tag::Short(self.i16()?),
+            3 => Tag::Int(self.i32()?),
+            4 => Tag::Long(self.i64()?),
+            5 => Tag::Float(f32::from_be_bytes(self.array()?)),
+            6 => Tag::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let len = self.len()?;
+                Tag::ByteArray(self.take(len)?.iter().map(|&b| b as i8).collect())
+            }
+            8 => Tag::String(self.string()?),
+            9 => {
+                let element = self.u8()?;
+                let len = self.len()?;
+                if element == 0 && len > 0 {
+                    return Err(invalid("non-empty list of TAG_End"));
+                }
+                let mut list = Vec::with_capacity(len.min(self.data.len()));
+                for _ in 0..len {
+                    list.push(self.payload(element, depth + 1)?);
+                }
+                Tag::List(list)
+            }
+            10 => {
+                let mut map = BTreeMap::new();
+                loop {
+                    let id = self.u8()?;
+                    if id == 0 {
+                        break;
+                    }
+                    let name = self.string()?;
+                    map.insert(name, self.payload(id, depth + 1)?);
+                }
+                Tag::Compound(map)
+            }
+            11 => {
+                let len = self.len()?;
+                let bytes = self.take(
+                    len.checked_mul(4)
+                        .ok_or_else(|| invalid("array too long"))?,
+                )?;
+                Tag::IntArray(
+                    bytes
+                        .as_chunks::<4>()
+                        .0
+                        .iter()
+                        .map(|b| i32::from_be_bytes(*b))
+                        .collect(),
+                )
+            }
+            12 => {
+                let len = self.len()?;
+                let bytes = self.take(
+                    len.checked_mul(8)
+                        .ok_or_else(|| invalid("array too long"))?,
+                )?;
+                Tag::LongArray(
+                    bytes
+                        .as_chunks::<8>()
+                        .0
+                        .iter()
+                        .map(|b| i64::from_be_bytes(*b))
+                        .collect(),
+                )
+            }
+            id => return Err(invalid(format!("unknown tag type {id}"))),
+        })
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) -> Result<()> {
+    let len = i32::try_from(len).map_err(|_| invalid("array or list too long"))?;
+    out.extend_from_slice(&len.to_be_bytes());
+    Ok(())
+}
+
+/// 字符串长度前缀为 u16，超过 65535 字节的字符串无法写出
+fn write_string(out: &mut Vec<u8>, s: &str) -> Result<()> {
+    let bytes = encode_mutf8(s);
+    let len = u16::try_from(bytes.len())
+        .map_err(|_| invalid(format!("string of {} bytes is too long", bytes.len())))?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(&bytes);
+    Ok(())
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) -> Result<()> {
+    match tag {
+        Tag::Byte(v) => out.push(*v as u8),
+        Tag::Short(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Long(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Double(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::ByteArray(v) => {
+            write_len(out, v.len())?;
+            out.extend(v.iter().map(|&b| b as u8));
+        }
+        Tag::String(s) => write_string(out, s)?,
+        Tag::List(list) => {
+            let element = list.first().map_or(0, Tag::id);
+            if list.iter().any(|tag| tag.id() != element) {
+                return Err(invalid("list elements must share one tag type"));
+            }
+            out.push(element);
+            write_len(out, list.len())?;
+            for tag in list {
+                write_payload(out, tag)?;
+            }
+        }
+        Tag::Compound(map) => {
+            for (name, tag) in map {
+                out.push(tag.id());
+                write_string(out, name)?;
+                write_payload(out, tag)?;
+            }
+            out.push(0);
+        }
+        Tag::IntArray(v) => {
+            write_len(out, v.len())?;
+            for i in v {
+                out.extend_from_slice(&i.to_be_bytes());
+            }
+        }
+        Tag::LongArray(v) => {
+            write_len(out, v.len())?;
+            for i in v {
+                out.extend_from_slice(&i.to_be_bytes());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Java 的 Modified UTF-8：`\0` 编码为两字节，补充平面字符拆为代理对
+fn encode_mutf8(s: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    for unit in s.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => out.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                out.push(0xC0 | (unit >> 6) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                out.push(0xE0 | (unit >> 12) as u8);
+                out.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+    out
+}
+
+fn decode_mutf8(bytes: &[u8]) -> Result<String> {
+    // 绝大多数字符串是纯 ASCII
+    if bytes.iter().all(|&b| b != 0 && b < 0x80) {
+        return Ok(String::from_utf8(bytes.to_vec()).expect("ascii"));
+    }
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter().copied();
+    let cont = |b: Option<u8>| match b {
+        Some(b) if b & 0xC0 == 0x80 => Ok((b & 0x3F) as u16),
+        _ => Err(invalid("malformed modified UTF-8")),
+    };
+    while let Some(b) = iter.next() {
+        let unit = match b {
+            0x01..=0x7F => b as u16,
+            0xC0..=0xDF => ((b as u16 & 0x1F) << 6) | cont(iter.next())?,
+            0xE0..=0xEF => {
+                ((b as u16 & 0x0F) << 12) | (cont(iter.next())? << 6) | cont(iter.next())?
+            }
+            _ => return Err(invalid("malformed modified UTF-8")),
+        };
+        units.push(unit);
+    }
+    String::from_utf16(&units).map_err(|_| invalid("unpaired surrogate in string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Tag {
+        Tag::Compound(BTreeMap::from([
+            ("byte".to_owned(), Tag::Byte(-1)),
+            ("short".to_owned(), Tag::Short(-300)),
+            ("int".to_owned(), Tag::Int(1 << 20)),
+            ("long".to_owned(), Tag::Long(-1 << 40)),
+            ("float".to_owned(), Tag::Float(1.5)),
+            ("double".to_owned(), Tag::Double(-0.25)),
+            ("bytes".to_owned(), Tag::ByteArray(vec![1, -2, 3])),
+            ("string".to_owned(), Tag::String("nul\0 ü 𝄞".to_owned())),
+            ("list".to_owned(), Tag::List(vec![Tag::Int(1), Tag::Int(2)])),
+            ("empty".to_owned(), Tag::List(Vec::new())),
+            (
+                "ints".to_owned(),
+                Tag::IntArray(vec![i32::MIN, 0, i32::MAX]),
+            ),
+            ("longs".to_owned(), Tag::LongArray(vec![i64::MIN, i64::MAX])),
+        ]))
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = write_nbt("root", &sample()).unwrap();
+        assert_eq!(read_nbt(&data).unwrap(), ("root".to_owned(), sample()));
+    }
+
+    #[test]
+    fn round_trip_compressed() {
+        for compression in [
+            NbtCompression::None,
+            NbtCompression::Gzip,
+            NbtCompression::Zlib,
+        ] {
+            let data = write_nbt_compressed("", &sample(), compression).unwrap();
+            assert_eq!(NbtCompression::detect(&data), compression);
+            let (name, tag, detected) = read_nbt_compressed(&data).unwrap();
+            assert_eq!((name.as_str(), detected), ("", compression));
+            assert_eq!(tag, sample());
+        }
+    }
+
+    #[test]
+    fn modified_utf8() {
+        // `\0` 占两个字节，补充平面字符按代理对各占三个字节
+        assert_eq!(encode_mutf8("\0"), [0xC0, 0x80]);
+        assert_eq!(encode_mutf8("𝄞").len(), 6);
+        assert_eq!(decode_mutf8(&encode_mutf8("a\0𝄞")).unwrap(), "a\0𝄞");
+    }
+
+    #[test]
+    fn rejects_long_strings() {
+        let tag = Tag::String("a".repeat(u16::MAX as usize + 1));
+        let root = Tag::Compound(BTreeMap::from([("s".to_owned(), tag)]));
+        assert!(write_nbt("", &root).is_err());
+    }
+
+    #[test]
+    fn rejects_mixed_lists() {
+        let list = Tag::List(vec![Tag::Int(1), Tag::String("2".to_owned())]);
+        let root = Tag::Compound(BTreeMap::from([("l".to_owned(), list)]));
+        assert!(write_nbt("", &root).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let data = write_nbt("", &sample()).unwrap();
+        assert!(read_nbt(&data[..data.len() - 1]).is_err());
+        assert!(read_nbt(&[8, 0, 0]).is_err());
+    }
+}
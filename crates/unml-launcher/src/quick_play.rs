@@ -0,0 +1,199 @@
+use unml_core::{Argument, QuickPlay, VersionInfo};
+
+use crate::{Error, Result};
+
+/// 1.20 之前的版本只能通过 `--server`/`--port` 直接加入服务器
+const DEFAULT_PORT: u16 = 25565;
+
+/// Quick Play 所需的游戏参数
+///
+/// 版本 JSON 的参数规则中声明了对应的 `is_quick_play_*` 特性时使用
+/// `--quickPlay*`，否则多人游戏回退到 `--server`/`--port`，其他目标不支持。
+pub fn quick_play_args(version: &VersionInfo, target: &QuickPlay) -> Result<Vec<String>> {
+    let (feature, placeholder, value) = match target {
+        QuickPlay::Singleplayer { world } => (
+            "is_quick_play_singleplayer",
+            "${quickPlaySingleplayer}",
+            world.clone(),
+        ),
+        QuickPlay::Multiplayer { host, port } => (
+            "is_quick_play_multiplayer",
+            "${quickPlayMultiplayer}",
+            port.map_or_else(|| host.clone(), |port| format!("{host}:{port}")),
+        ),
+        QuickPlay::Realms { realm_id } => (
+            "is_quick_play_realms",
+            "${quickPlayRealms}",
+            realm_id.clone(),
+        ),
+    };
+
+    let gated: Vec<&Argument> = version
+        .arguments
+        .iter()
+        .flat_map(|arguments| &arguments.game)
+        .filter(|argument| match argument {
+            Argument::Conditional { rules, .. } => {
+                rules.iter().any(|rule| rule.features.contains_key(feature))
+            }
+            Argument::Plain(_) => false,
+        })
+        .collect();
+
+    if !gated.is_empty() {
+        return Ok(gated
+            .into_iter()
+            .flat_map(|argument| argument.values(&[feature]))
+            .map(|arg| arg.replace(placeholder, &value))
+            .collect());
+    }
+
+    match target {
+        QuickPlay::Multiplayer { host, port } => Ok(vec![
+            "--server".to_owned(),
+            host.clone(),
+            "--port".to_owned(),
+            port.unwrap_or(DEFAULT_PORT).to_string(),
+        ]),
+        _ => Err(Error::QuickPlayUnsupported(version.id.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn version(id: &str, extra: serde_json::Value) -> VersionInfo {
+        let mut json = json!({
+            "id": id,
+            "type": "release",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [],
+            "assetIndex": {"id": "1", "url": "", "sha1": "", "totalSize": 0}
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn singleplayer() -> QuickPlay {
+        QuickPlay::Singleplayer {
+            world: "New World".to_owned(),
+        }
+    }
+
+    fn multiplayer(port: Option<u16>) -> QuickPlay {
+        QuickPlay::Multiplayer {
+            host: "mc.example.com".to_owned(),
+            port,
+        }
+    }
+
+    #[test]
+    fn quick_play_rules() {
+        let version = version(
+            "1.20.4",
+            json!({
+                "arguments": {
+                    "game": [
+                        "--username", "${auth_player_name}",
+                        {
+                            "rules": [{"action": "allow", "features": {"has_quick_plays_support": true}}],
+                            "value": ["--quickPlayPath", "${quickPlayPath}"]
+                        },
+                        {
+                            "rules": [{"action": "allow", "features": {"is_quick_play_singleplayer": true}}],
+                            "value": ["--quickPlaySingleplayer", "${quickPlaySingleplayer}"]
+                        },
+                        {
+                            "rules": [{"action": "allow", "features": {"is_quick_play_multiplayer": true}}],
+                            "value": ["--quickPlayMultiplayer", "${quickPlayMultiplayer}"]
+                        },
+                        {
+                            "rules": [{"action": "allow", "features": {"is_quick_play_realms": true}}],
+                            "value": ["--quickPlayRealms", "${quickPlayRealms}"]
+                        }
+                    ],
+                    "jvm": []
+                }
+            }),
+        );
+
+        assert_eq!(
+            quick_play_args(&version, &singleplayer()).unwrap(),
+            ["--quickPlaySingleplayer", "New World"]
+        );
+        assert_eq!(
+            quick_play_args(&version, &multiplayer(Some(25566))).unwrap(),
+            ["--quickPlayMultiplayer", "mc.example.com:25566"]
+        );
+        assert_eq!(
+            quick_play_args(&version, &multiplayer(None)).unwrap(),
+            ["--quickPlayMultiplayer", "mc.example.com"]
+        );
+        assert_eq!(
+            quick_play_args(
+                &version,
+                &QuickPlay::Realms {
+                    realm_id: "42".to_owned()
+                }
+            )
+            .unwrap(),
+            ["--quickPlayRealms", "42"]
+        );
+    }
+
+    #[test]
+    fn modern_without_rules_falls_back_to_server() {
+        let version = version(
+            "1.19.4",
+            json!({
+                "arguments": {
+                    "game": [
+                        "--username", "${auth_player_name}",
+                        {
+                            "rules": [{"action": "allow", "features": {"has_custom_resolution": true}}],
+                            "value": ["--width", "${resolution_width}"]
+                        }
+                    ],
+                    "jvm": []
+                }
+            }),
+        );
+
+        assert_eq!(
+            quick_play_args(&version, &multiplayer(None)).unwrap(),
+            ["--server", "mc.example.com", "--port", "25565"]
+        );
+        assert_eq!(
+            quick_play_args(&version, &multiplayer(Some(25566))).unwrap(),
+            ["--server", "mc.example.com", "--port", "25566"]
+        );
+        assert!(matches!(
+            quick_play_args(&version, &singleplayer()),
+            Err(Error::QuickPlayUnsupported(id)) if id == "1.19.4"
+        ));
+    }
+
+    #[test]
+    fn legacy_arguments_fall_back_to_server() {
+        let version = version(
+            "1.12.2",
+            json!({
+                "minecraftArguments": "--username ${auth_player_name} --version ${version_name}"
+            }),
+        );
+
+        assert_eq!(
+            quick_play_args(&version, &multiplayer(Some(25566))).unwrap(),
+            ["--server", "mc.example.com", "--port", "25566"]
+        );
+        assert!(matches!(
+            quick_play_args(&version, &singleplayer()),
+            Err(Error::QuickPlayUnsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,134 @@
+mod install;
+mod properties;
+
+use std::path::{Path, PathBuf};
+
+pub use properties::{Difficulty, GameMode, ServerProperties};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use unml_core::{GameProcess, LoaderSpec};
+
+use crate::{Error, Result};
+
+/// 记录服务端安装信息的文件
+const SERVER_FILE: &str = "unml-server.json";
+const PROPERTIES_FILE: &str = "server.properties";
+const EULA_FILE: &str = "eula.txt";
+
+/// 服务端的启动方式，路径相对于服务端目录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerLaunch {
+    /// `java -jar <jar>`
+    Jar { jar: String },
+    /// Forge/NeoForge 1.17+ 的参数文件目录，启动时按平台选择
+    /// `unix_args.txt` 或 `win_args.txt`
+    ArgsFile { dir: String },
+}
+
+impl ServerLaunch {
+    /// 主类之前的启动参数，`windows` 决定 Forge/NeoForge 参数文件的选择
+    pub fn args(&self, windows: bool) -> Vec<String> {
+        match self {
+            Self::Jar { jar } => vec!["-jar".to_owned(), jar.clone()],
+            Self::ArgsFile { dir } => {
+                let file = if windows {
+                    "win_args.txt"
+                } else {
+                    "unix_args.txt"
+                };
+                vec![format!("@{dir}/{file}")]
+            }
+        }
+    }
+}
+
+/// 已安装的服务端
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerInstall {
+    pub game_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<LoaderSpec>,
+    pub launch: ServerLaunch,
+}
+
+/// 专用服务端目录
+pub struct DedicatedServer {
+    dir: PathBuf,
+}
+
+impl DedicatedServer {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 已安装的服务端，未安装时返回 `None`
+    pub async fn installed(&self) -> Result<Option<ServerInstall>> {
+        match tokio::fs::read(self.dir.join(SERVER_FILE)).await {
+            Ok(data) => Ok(Some(
+                serde_json::from_slice(&data).map_err(unml_core::JsonError)?,
+            )),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn properties(&self) -> Result<ServerProperties> {
+        ServerProperties::load(&self.dir.join(PROPERTIES_FILE)).await
+    }
+
+    pub async fn save_properties(&self, properties: &ServerProperties) -> Result<()> {
+        let game_version = self.installed().await?.map(|install| install.game_version);
+        properties
+            .save(&self.dir.join(PROPERTIES_FILE), game_version.as_deref())
+            .await
+    }
+
+    pub async fn eula_accepted(&self) -> Result<bool> {
+        let content = match tokio::fs::read_to_string(self.dir.join(EULA_FILE)).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .any(|(key, value)| key.trim() == "eula" && value.trim().eq_ignore_ascii_case("true")))
+    }
+
+    /// 代表用户同意 Minecraft EULA，调用前应先向用户展示
+    pub async fn accept_eula(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        tokio::fs::write(
+            self.dir.join(EULA_FILE),
+            "#By changing the setting below to TRUE you are indicating your agreement to our EULA (https://aka.ms/MinecraftEULA).\neula=true\n",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 启动服务端，需要先安装并同意 EULA
+    ///
+    /// 返回的进程接管了控制台，通过 [`GameProcess::console`] 发送命令。
+    pub async fn launch(&self, java: &Path, jvm_args: &[String]) -> Result<GameProcess> {
+        let install = self.installed().await?.ok_or(Error::ServerNotInstalled)?;
+        if !self.eula_accepted().await? {
+            return Err(Error::EulaNotAccepted);
+        }
+
+        let mut command = Command::new(java);
+        command
+            .args(jvm_args)
+            .args(install.launch.args(cfg!(windows)))
+            .arg("nogui")
+            .current_dir(&self.dir);
+
+        GameProcess::spawn(command).map_err(|e| Error::LaunchFailed(e.to_string()))
+    }
+}
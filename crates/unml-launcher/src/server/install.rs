@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use unml_core::{Checksum, DownloadProvider, LoaderSpec, ModLoader};
+
+use super::{DedicatedServer, SERVER_FILE, ServerInstall, ServerLaunch};
+use crate::{Error, Result};
+
+const SERVER_JAR: &str = "server.jar";
+const FABRIC_LAUNCH_JAR: &str = "fabric-server-launch.jar";
+/// Fabric 服务端启动器对应的安装器版本
+const FABRIC_INSTALLER_VERSION: &str = "1.0.1";
+
+/// Forge/NeoForge 安装器在 Maven 仓库中的位置
+struct InstallerArtifact {
+    repository: &'static str,
+    group_path: &'static str,
+    artifact: &'static str,
+    version: String,
+}
+
+impl InstallerArtifact {
+    fn new(loader: &LoaderSpec, game_version: &str) -> Option<Self> {
+        let artifact = match loader.loader {
+            ModLoader::Forge => Self {
+                repository: "https://maven.minecraftforge.net",
+                group_path: "net/minecraftforge/forge",
+                artifact: "forge",
+                version: format!("{game_version}-{}", loader.version),
+            },
+            // 1.20.1 的 NeoForge 沿用 Forge 的坐标
+            ModLoader::NeoForge if game_version == "1.20.1" => Self {
+                repository: "https://maven.neoforged.net/releases",
+                group_path: "net/neoforged/forge",
+                artifact: "forge",
+                version: format!("{game_version}-{}", loader.version),
+            },
+            ModLoader::NeoForge => Self {
+                repository: "https://maven.neoforged.net/releases",
+                group_path: "net/neoforged/neoforge",
+                artifact: "neoforge",
+                version: loader.version.clone(),
+            },
+            ModLoader::Fabric | ModLoader::Quilt => return None,
+        };
+
+        Some(artifact)
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}-{}-installer.jar", self.artifact, self.version)
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}/{}/{}/{}",
+            self.repository,
+            self.group_path,
+            self.version,
+            self.file_name()
+        )
+    }
+
+    /// 1.17+ 安装器生成的参数文件所在目录
+    fn args_dir(&self) -> String {
+        format!("libraries/{}/{}", self.group_path, self.version)
+    }
+}
+
+impl DedicatedServer {
+    /// 安装服务端
+    ///
+    /// 原版与 Fabric 使用版本 JSON 中的 `downloads.server`；Forge/NeoForge
+    /// 下载官方安装器并用 `java` 运行 `--installServer`。
+    pub async fn install<P>(
+        &self,
+        provider: &P,
+        game_version: &str,
+        loader: Option<&LoaderSpec>,
+        java: &Path,
+    ) -> Result<ServerInstall>
+    where
+        P: DownloadProvider<Error = unml_download::Error>,
+    {
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        let launch = match loader {
+            None => {
+                self.install_vanilla(provider, game_version).await?;
+                ServerLaunch::Jar {
+                    jar: SERVER_JAR.to_owned(),
+                }
+            }
+            Some(spec) if spec.loader == ModLoader::Fabric => {
+                self.install_vanilla(provider, game_version).await?;
+                let url = format!(
+                    "https://meta.fabricmc.net/v2/versions/loader/{game_version}/{}/{FABRIC_INSTALLER_VERSION}/server/jar",
+                    spec.version
+                );
+                provider
+                    .download_file(&url, &self.dir.join(FABRIC_LAUNCH_JAR), None, None)
+                    .await?;
+                ServerLaunch::Jar {
+                    jar: FABRIC_LAUNCH_JAR.to_owned(),
+                }
+            }
+            Some(spec) => {
+                let artifact = InstallerArtifact::new(spec, game_version)
+                    .ok_or_else(|| Error::UnsupportedLoader(format!("{:?}", spec.loader)))?;
+                self.run_installer(provider, &artifact, java).await?
+            }
+        };
+
+        let install = ServerInstall {
+            game_version: game_version.to_owned(),
+            loader: loader.cloned(),
+            launch,
+        };
+        let data = serde_json::to_vec_pretty(&install).map_err(unml_core::JsonError)?;
+        tokio::fs::write(self.dir.join(SERVER_FILE), data).await?;
+
+        Ok(install)
+    }
+
+    async fn install_vanilla<P>(&self, provider: &P, game_version: &str) -> Result<()>
+    where
+        P: DownloadProvider<Error = unml_download::Error>,
+    {
+        let version = provider.fetch_version_info(game_version).await?;
+        let server = version
+            .downloads
+            .server
+            .ok_or_else(|| Error::ServerUnavailable(game_version.to_owned()))?;
+
+        provider
+            .download_file(
+                &provider.transform_url(&server.url),
+                &self.dir.join(SERVER_JAR),
+                Some(&Checksum::Sha1(server.sha1)),
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn run_installer<P>(
+        &self,
+        provider: &P,
+        artifact: &InstallerArtifact,
+        java: &Path,
+    ) -> Result<ServerLaunch>
+    where
+        P: DownloadProvider<Error = unml_download::Error>,
+    {
+        let installer = self.dir.join(artifact.file_name());
+        provider
+            .download_file(&artifact.url(), &installer, None, None)
+            .await?;
+
+        let output = tokio::process::Command::new(java)
+            .arg("-jar")
+            .arg(&installer)
+            .arg("--installServer")
+            .arg(&self.dir)
+            .current_dir(&self.dir)
+            .output()
+            .await?;
+        if !output.status.success() {
+            let log = String::from_utf8_lossy(&output.stdout);
+            let tail: Vec<&str> = log.lines().rev().take(5).collect();
+            return Err(Error::ServerInstallFailed(
+                tail.into_iter().rev().collect::<Vec<_>>().join("\n"),
+            ));
+        }
+        tokio::fs::remove_file(&installer).await?;
+
+        let args_dir = artifact.args_dir();
+        if tokio::fs::try_exists(self.dir.join(&args_dir).join("unix_args.txt")).await? {
+            return Ok(ServerLaunch::ArgsFile { dir: args_dir });
+        }
+
+        // 旧版本安装器生成可直接运行的 jar
+        let prefix = format!("{}-{}", artifact.artifact, artifact.version);
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with(&prefix) && name.ends_with(".jar") && !name.contains("installer") {
+                return Ok(ServerLaunch::Jar { jar: name });
+            }
+        }
+
+        Err(Error::ServerInstallFailed(
+            "installer did not produce a launchable server".to_owned(),
+        ))
+    }
+}
@@ -0,0 +1,414 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::Result;
+
+/// 难度，1.14 起写为名称，更早的版本只能解析数字
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Peaceful => "peaceful",
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Hard => "hard",
+        }
+    }
+
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    /// 兼容旧版本的数字形式
+    fn from_str(s: &str) -> std::result::Result<Self, ()> {
+        match s {
+            "peaceful" | "0" => Ok(Self::Peaceful),
+            "easy" | "1" => Ok(Self::Easy),
+            "normal" | "2" => Ok(Self::Normal),
+            "hard" | "3" => Ok(Self::Hard),
+            _ => Err(()),
+        }
+    }
+}
+
+/// 默认游戏模式，与难度一样在 1.14 之前写为数字
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl GameMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Survival => "survival",
+            Self::Creative => "creative",
+            Self::Adventure => "adventure",
+            Self::Spectator => "spectator",
+        }
+    }
+
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, ()> {
+        match s {
+            "survival" | "0" => Ok(Self::Survival),
+            "creative" | "1" => Ok(Self::Creative),
+            "adventure" | "2" => Ok(Self::Adventure),
+            "spectator" | "3" => Ok(Self::Spectator),
+            _ => Err(()),
+        }
+    }
+}
+
+/// `server.properties`，常用项有类型，其余项原样保留
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerProperties {
+    pub motd: String,
+    pub server_ip: String,
+    pub server_port: u16,
+    pub max_players: u32,
+    pub online_mode: bool,
+    pub difficulty: Difficulty,
+    pub gamemode: GameMode,
+    pub hardcore: bool,
+    pub pvp: bool,
+    pub white_list: bool,
+    pub enforce_whitelist: bool,
+    pub level_name: String,
+    pub level_seed: String,
+    pub view_distance: u32,
+    pub simulation_distance: u32,
+    pub spawn_protection: u32,
+    pub allow_flight: bool,
+    pub enable_command_block: bool,
+    /// 其他未列出的项
+    pub extra: BTreeMap<String, String>,
+}
+
+impl Default for ServerProperties {
+    /// 与原版服务端首次启动生成的默认值一致
+    fn default() -> Self {
+        Self {
+            motd: "A Minecraft Server".to_owned(),
+            server_ip: String::new(),
+            server_port: 25565,
+            max_players: 20,
+            online_mode: true,
+            difficulty: Difficulty::Easy,
+            gamemode: GameMode::Survival,
+            hardcore: false,
+            pvp: true,
+            white_list: false,
+            enforce_whitelist: false,
+            level_name: "world".to_owned(),
+            level_seed: String::new(),
+            view_distance: 10,
+            simulation_distance: 10,
+            spawn_protection: 16,
+            allow_flight: false,
+            enable_command_block: false,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+impl ServerProperties {
+    /// 读取文件，不存在时返回默认值
+    pub async fn load(path: &Path) -> Result<Self> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 写入文件，`game_version` 为服务端版本，用于选择难度与游戏模式的格式
+    pub async fn save(&self, path: &Path, game_version: Option<&str>) -> Result<()> {
+        tokio::fs::write(path, self.to_properties(game_version)).await?;
+        Ok(())
+    }
+
+    /// 解析 Java properties 格式，无法解析的值使用默认值
+    pub fn parse(content: &str) -> Self {
+        let mut entries: BTreeMap<String, String> = content
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| !line.is_empty() && !line.starts_with(['#', '!']))
+            .map(|line| {
+                let (key, value) = split_entry(line);
+                (unescape(key), unescape(value))
+            })
+            .collect();
+
+        let mut properties = Self::default();
+        macro_rules! take {
+            ($key:literal => $field:ident) => {
+                if let Some(value) = entries.remove($key)
+                    && let Ok(value) = value.parse()
+                {
+                    properties.$field = value;
+                }
+            };
+        }
+        take!("motd" => motd);
+        take!("server-ip" => server_ip);
+        take!("server-port" => server_port);
+        take!("max-players" => max_players);
+        take!("online-mode" => online_mode);
+        take!("difficulty" => difficulty);
+        take!("gamemode" => gamemode);
+        take!("hardcore" => hardcore);
+        take!("pvp" => pvp);
+        take!("white-list" => white_list);
+        take!("enforce-whitelist" => enforce_whitelist);
+        take!("level-name" => level_name);
+        take!("level-seed" => level_seed);
+        take!("view-distance" => view_distance);
+        take!("simulation-distance" => simulation_distance);
+        take!("spawn-protection" => spawn_protection);
+        take!("allow-flight" => allow_flight);
+        take!("enable-command-block" => enable_command_block);
+        properties.extra = entries;
+
+        properties
+    }
+
+    /// 序列化为 properties 格式，按键名排序
+    ///
+    /// 1.14 之前的服务端只接受数字形式的难度与游戏模式，版本未知时使用名称。
+    pub fn to_properties(&self, game_version: Option<&str>) -> String {
+        let (difficulty, gamemode) = if game_version.is_some_and(numeric_enums) {
+            (
+                self.difficulty.id().to_string(),
+                self.gamemode.id().to_string(),
+            )
+        } else {
+            (
+                self.difficulty.as_str().to_owned(),
+                self.gamemode.as_str().to_owned(),
+            )
+        };
+
+        let mut entries = self.extra.clone();
+        let typed = [
+            ("motd", self.motd.clone()),
+            ("server-ip", self.server_ip.clone()),
+            ("server-port", self.server_port.to_string()),
+            ("max-players", self.max_players.to_string()),
+            ("online-mode", self.online_mode.to_string()),
+            ("difficulty", difficulty),
+            ("gamemode", gamemode),
+            ("hardcore", self.hardcore.to_string()),
+            ("pvp", self.pvp.to_string()),
+            ("white-list", self.white_list.to_string()),
+            ("enforce-whitelist", self.enforce_whitelist.to_string()),
+            ("level-name", self.level_name.clone()),
+            ("level-seed", self.level_seed.clone()),
+            ("view-distance", self.view_distance.to_string()),
+            ("simulation-distance", self.simulation_distance.to_string()),
+            ("spawn-protection", self.spawn_protection.to_string()),
+            ("allow-flight", self.allow_flight.to_string()),
+            (
+                "enable-command-block",
+                self.enable_command_block.to_string(),
+            ),
+        ];
+        entries.extend(typed.map(|(key, value)| (key.to_owned(), value)));
+
+        let mut out = String::from("#Minecraft server properties\n");
+        for (key, value) in entries {
+            let _ = writeln!(out, "{}={}", escape(&key, true), escape(&value, false));
+        }
+
+        out
+    }
+}
+
+/// 1.14 之前的正式版本，快照等无法判断的版本按新格式处理
+fn numeric_enums(game_version: &str) -> bool {
+    let mut parts = game_version.split('.');
+    parts.next() == Some("1")
+        && parts
+            .next()
+            .and_then(|minor| minor.parse::<u32>().ok())
+            .is_some_and(|minor| minor < 14)
+}
+
+/// 按第一个未转义的 `=`、`:` 或空白分隔键和值
+fn split_entry(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' | ':' | ' ' | '\t' => {
+                let value = line[i + 1..].trim_start();
+                let value = match c {
+                    ' ' | '\t' => value.strip_prefix(['=', ':']).unwrap_or(value),
+                    _ => value,
+                };
+                return (&line[..i], value.trim_start());
+            }
+            _ => {}
+        }
+    }
+
+    (line, "")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    // 连续的 `\uXXXX` 可能是代理对，需要一起解码
+    let mut units = Vec::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        let next = if c == '\\' { chars.next() } else { None };
+        if next == Some('u') {
+            let hex: String = chars.by_ref().take(4).collect();
+            if let Ok(unit) = u16::from_str_radix(&hex, 16) {
+                units.push(unit);
+            }
+            continue;
+        }
+
+        out.extend(char::decode_utf16(units.drain(..)).map(|c| c.unwrap_or('\u{fffd}')));
+        match (c, next) {
+            ('\\', Some('n')) => out.push('\n'),
+            ('\\', Some('t')) => out.push('\t'),
+            ('\\', Some('r')) => out.push('\r'),
+            ('\\', Some(c)) => out.push(c),
+            ('\\', None) => {}
+            (c, _) => out.push(c),
+        }
+    }
+    out.extend(char::decode_utf16(units).map(|c| c.unwrap_or('\u{fffd}')));
+
+    out
+}
+
+/// 非 ASCII 字符写为 `\uXXXX`，旧版本服务端按 ISO-8859-1 读取
+fn escape(s: &str, key: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '=' | ':' if key => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' ' if key || i == 0 => out.push_str("\\ "),
+            '#' | '!' if key && i == 0 => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_ascii() => out.push(c),
+            c => {
+                let mut buf = [0; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    let _ = write!(out, "\\u{unit:04x}");
+                }
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separators() {
+        let properties = ServerProperties::parse(
+            "#comment\n! also a comment\nmotd=Hello\nlevel-name:saves\nmax-players 8\nlevel-seed  =  42\nview-distance\t: 12\nwhite-list\n",
+        );
+        assert_eq!(properties.motd, "Hello");
+        assert_eq!(properties.level_name, "saves");
+        assert_eq!(properties.max_players, 8);
+        assert_eq!(properties.level_seed, "42");
+        assert_eq!(properties.view_distance, 12);
+        // 无法解析的值保持默认
+        assert!(!properties.white_list);
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let properties = ServerProperties::parse("motd=\\u00a7aHi \\ud83d\\ude00\\u4f60\\u597d\n");
+        assert_eq!(properties.motd, "§aHi 😀你好");
+
+        let content = properties.to_properties(None);
+        assert!(content.contains("motd=\\u00a7aHi \\ud83d\\ude00\\u4f60\\u597d\n"));
+        assert_eq!(ServerProperties::parse(&content), properties);
+
+        // 不成对的代理项替换为 U+FFFD
+        let properties = ServerProperties::parse("motd=\\ud83d!\n");
+        assert_eq!(properties.motd, "\u{fffd}!");
+    }
+
+    #[test]
+    fn escaped_keys_round_trip() {
+        let mut properties = ServerProperties {
+            motd: " leading space\\ and\ttab".to_owned(),
+            ..Default::default()
+        };
+        properties
+            .extra
+            .insert("a=b:c d".to_owned(), "x=y:z".to_owned());
+        properties.extra.insert("#key".to_owned(), "!".to_owned());
+
+        let content = properties.to_properties(None);
+        assert!(content.contains("a\\=b\\:c\\ d=x=y:z\n"));
+        assert!(content.contains("\\#key=!\n"));
+        assert_eq!(ServerProperties::parse(&content), properties);
+
+        let parsed = ServerProperties::parse("key\\:with\\=sep value\n");
+        assert_eq!(parsed.extra["key:with=sep"], "value");
+    }
+
+    #[test]
+    fn enum_format_follows_version() {
+        let properties = ServerProperties {
+            difficulty: Difficulty::Hard,
+            gamemode: GameMode::Creative,
+            ..Default::default()
+        };
+
+        for version in [None, Some("1.14"), Some("1.20.4"), Some("24w14a")] {
+            let content = properties.to_properties(version);
+            assert!(content.contains("difficulty=hard\n"), "{version:?}");
+            assert!(content.contains("gamemode=creative\n"), "{version:?}");
+        }
+        for version in ["1.12.2", "1.7.10", "1.13"] {
+            let content = properties.to_properties(Some(version));
+            assert!(content.contains("difficulty=3\n"), "{version}");
+            assert!(content.contains("gamemode=1\n"), "{version}");
+            assert_eq!(ServerProperties::parse(&content), properties);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::nbt::{Tag, read_nbt_compressed};
+use crate::{Difficulty, Error, GameMode, Result};
+
+/// 游戏目录下的存档目录
+pub const SAVES_DIR: &str = "saves";
+const LEVEL_FILE: &str = "level.dat";
+/// 游戏保存 `level.dat` 前留下的备份
+const LEVEL_BACKUP_FILE: &str = "level.dat_old";
+const ICON_FILE: &str = "icon.png";
+
+/// `saves/` 中的一个存档
+#[derive(Debug, Clone)]
+pub struct WorldInfo {
+    /// 存档目录名，Quick Play 进入单人世界时使用
+    pub folder: String,
+    pub path: PathBuf,
+    /// 游戏内显示的世界名称
+    pub name: String,
+    pub game_mode: Option<GameMode>,
+    pub hardcore: bool,
+    pub difficulty: Option<Difficulty>,
+    pub last_played: Option<SystemTime>,
+    /// 1.9 之前的存档没有版本信息
+    pub version_name: Option<String>,
+    pub data_version: Option<i32>,
+    pub seed: Option<i64>,
+    /// 存档目录的总大小（字节）
+    pub size: u64,
+    pub icon: Option<PathBuf>,
+}
+
+/// 扫描存档目录，按最后游玩时间倒序排列
+///
+/// 没有可读 `level.dat` 的目录会被跳过。
+pub async fn scan_worlds(saves: &Path) -> Result<Vec<WorldInfo>> {
+    let mut worlds = Vec::new();
+    if !tokio::fs::try_exists(saves).await? {
+        return Ok(worlds);
+    }
+
+    let mut entries = tokio::fs::read_dir(saves).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir()
+            && let Ok(world) = read_world(&entry.path()).await
+        {
+            worlds.push(world);
+        }
+    }
+    worlds.sort_by_key(|w| Reverse(w.last_played));
+
+    Ok(worlds)
+}
+
+/// 读取单个存档，`level.dat` 损坏时回退到 `level.dat_old`
+pub async fn read_world(dir: &Path) -> Result<WorldInfo> {
+    let data = match read_level(&dir.join(LEVEL_FILE)).await {
+        Ok(data) => data,
+        Err(e) => read_level(&dir.join(LEVEL_BACKUP_FILE))
+            .await
+            .map_err(|_| e)?,
+    };
+
+    let folder = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let get = |key: &str| data.get(key);
+    // 兼容将难度与极限模式存放在 `difficulty_settings` 中的新版本存档
+    let settings = get("difficulty_settings").and_then(Tag::as_compound);
+
+    let difficulty = match get("Difficulty").and_then(Tag::as_i64) {
+        Some(id) => id.to_string().parse().ok(),
+        None => settings
+            .and_then(|s| s.get("difficulty"))
+            .and_then(Tag::as_str)
+            .and_then(|s| s.parse().ok()),
+    };
+    let hardcore = get("hardcore")
+        .or_else(|| settings.and_then(|s| s.get("hardcore")))
+        .and_then(Tag::as_bool)
+        .unwrap_or(false);
+    let version = get("Version").and_then(Tag::as_compound);
+    // 1.16 起种子位于 `WorldGenSettings`
+    let seed = get("WorldGenSettings")
+        .and_then(Tag::as_compound)
+        .and_then(|s| s.get("seed"))
+        .or_else(|| get("RandomSeed"))
+        .and_then(Tag::as_i64);
+    let icon = dir.join(ICON_FILE);
+
+    Ok(WorldInfo {
+        name: get("LevelName")
+            .and_then(Tag::as_str)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&folder)
+            .to_owned(),
+        game_mode: get("GameType")
+            .and_then(Tag::as_i64)
+            .and_then(|id| id.to_string().parse().ok()),
+        hardcore,
+        difficulty,
+        last_played: get("LastPlayed")
+            .and_then(Tag::as_i64)
+            .and_then(|ms| u64::try_from(ms).ok())
+            .map(|ms| UNIX_EPOCH + Duration::from_millis(ms)),
+        version_name: version
+            .and_then(|v| v.get("Name"))
+            .and_then(Tag::as_str)
+            .map(str::to_owned),
+        data_version: get("DataVersion")
+            .and_then(Tag::as_i64)
+            .and_then(|v| i32::try_from(v).ok()),
+        seed,
+        size: dir_size(dir).await?,
+        icon: tokio::fs::try_exists(&icon).await?.then_some(icon),
+        path: dir.to_path_buf(),
+        folder,
+    })
+}
+
+/// `level.dat` 中的 `Data` 复合标签
+async fn read_level(path: &Path) -> Result<BTreeMap<String, Tag>> {
+    let bytes = tokio::fs::read(path).await?;
+    let (_, root, _) = read_nbt_compressed(&bytes)?;
+    match root {
+        Tag::Compound(mut root) => match root.remove("Data") {
+            Some(Tag::Compound(data)) => Ok(data),
+            _ => Err(Error::InvalidNbt("level.dat has no Data compound".into())),
+        },
+        _ => Err(Error::InvalidNbt("level.dat root is not a compound".into())),
+    }
+}
+
+async fn dir_size(dir: &Path) -> Result<u64> {
+    let mut size = 0;
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if metadata.is_dir() {
+                stack.push(entry.path());
+            } else {
+                size += metadata.len();
+            }
+        }
+    }
+
+    Ok(size)
+}
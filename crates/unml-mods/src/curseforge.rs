@@ -1,15 +1,211 @@
 use std::path::Path;
 
 use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use unml_core::{ModDetail, ModInfo, ModPlatform, ModVersion, ProgressCallback, SearchFilters};
 
-use crate::{Error, Result};
+use crate::{Error, Result, http_client};
 
-pub struct CurseForgePlatform;
+/// CurseForge 文件哈希算法：1 为 SHA-1，2 为 MD5
+const HASH_ALGO_SHA1: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<FingerprintMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FingerprintMatch {
+    file: CurseForgeFile,
+}
+
+/// CurseForge 文件指纹：去掉空白字符（`\t`、`\n`、`\r`、空格）后的
+/// MurmurHash2，种子为 1
+pub fn fingerprint(data: &[u8]) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+
+    let data: Vec<u8> = data
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, b'\t' | b'\n' | b'\r' | b' '))
+        .collect();
+
+    let mut hash = 1 ^ data.len() as u32;
+    let (chunks, rest) = data.as_chunks::<4>();
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes(*chunk);
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        hash = hash.wrapping_mul(M) ^ k;
+    }
+
+    if !rest.is_empty() {
+        for (i, b) in rest.iter().enumerate() {
+            hash ^= u32::from(*b) << (8 * i);
+        }
+        hash = hash.wrapping_mul(M);
+    }
+
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(M);
+    hash ^ (hash >> 15)
+}
+
+/// CurseForge 上的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u64,
+    pub mod_id: u64,
+    pub display_name: String,
+    pub file_name: String,
+    /// 作者禁止第三方分发时为空，只能手动下载
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeHash>,
+    #[serde(default)]
+    pub file_length: u64,
+    /// 见 [`fingerprint`]
+    #[serde(default)]
+    pub file_fingerprint: u32,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes
+            .iter()
+            .find(|hash| hash.algo == HASH_ALGO_SHA1)
+            .map(|hash| hash.value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurseForgeHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+/// CurseForge 上的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMod {
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    /// 项目类别：6 为 Mod，12 为资源包，6552 为光影包
+    pub class_id: Option<u64>,
+    #[serde(default)]
+    pub links: CurseForgeLinks,
+}
+
+impl CurseForgeMod {
+    /// 该类项目在游戏目录中的存放目录
+    pub fn install_dir(&self) -> &'static str {
+        match self.class_id {
+            Some(12) => "resourcepacks",
+            Some(6552) => "shaderpacks",
+            _ => "mods",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeLinks {
+    pub website_url: Option<String>,
+}
+
+pub struct CurseForgePlatform {
+    base_url: String,
+    api_key: Option<String>,
+}
 
 impl CurseForgePlatform {
     pub fn new() -> Self {
-        Self
+        Self {
+            base_url: "https://api.curseforge.com".to_owned(),
+            api_key: None,
+        }
+    }
+
+    /// CurseForge API 需要 API Key
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// 批量获取文件信息
+    pub async fn get_files(&self, file_ids: &[u64]) -> Result<Vec<CurseForgeFile>> {
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.post(
+            "/v1/mods/files",
+            &serde_json::json!({ "fileIds": file_ids }),
+        )
+        .await
+    }
+
+    /// 批量获取项目信息
+    pub async fn get_mods(&self, mod_ids: &[u64]) -> Result<Vec<CurseForgeMod>> {
+        if mod_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.post("/v1/mods", &serde_json::json!({ "modIds": mod_ids }))
+            .await
+    }
+
+    /// 按 [`fingerprint`] 批量识别文件，只返回完全匹配的文件
+    pub async fn match_fingerprints(&self, fingerprints: &[u32]) -> Result<Vec<CurseForgeFile>> {
+        if fingerprints.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let matches: FingerprintMatches = self
+            .post(
+                "/v1/fingerprints",
+                &serde_json::json!({ "fingerprints": fingerprints }),
+            )
+            .await?;
+
+        Ok(matches.exact_matches.into_iter().map(|m| m.file).collect())
+    }
+
+    async fn post<T: DeserializeOwned>(&self, path: &str, body: &serde_json::Value) -> Result<T> {
+        let api_key = self.api_key.as_deref().ok_or(Error::ApiKeyRequired)?;
+
+        let bytes = http_client()
+            .post(format!("{}{path}", self.base_url))
+            .header("x-api-key", api_key)
+            .json(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| unml_core::HttpError(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+        let response: DataResponse<T> =
+            serde_json::from_slice(&bytes).map_err(unml_core::JsonError)?;
+
+        Ok(response.data)
     }
 }
 
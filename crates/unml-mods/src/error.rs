@@ -8,6 +8,15 @@ pub enum Error {
     #[error("Version not found: {0}")]
     VersionNotFound(String),
 
+    #[error("Invalid modpack: {0}")]
+    InvalidModpack(String),
+
+    #[error("A CurseForge API key is required")]
+    ApiKeyRequired,
+
+    #[error("All download sources failed for {0}")]
+    DownloadFailed(String),
+
     #[error(transparent)]
     Io(#[from] unml_core::IoError),
 
@@ -16,6 +25,21 @@ pub enum Error {
 
     #[error(transparent)]
     Json(#[from] unml_core::JsonError),
+
+    #[error(transparent)]
+    Checksum(#[from] unml_core::ChecksumError),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(unml_core::IoError(e))
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(e: zip::result::ZipError) -> Self {
+        Error::InvalidModpack(e.to_string())
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
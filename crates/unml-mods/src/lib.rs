@@ -1,7 +1,33 @@
 mod curseforge;
 mod error;
+mod metadata;
+mod modpack;
 mod modrinth;
 
-pub use curseforge::CurseForgePlatform;
+use std::sync::OnceLock;
+
+pub use curseforge::{
+    CurseForgeFile, CurseForgeHash, CurseForgeLinks, CurseForgeMod, CurseForgePlatform, fingerprint,
+};
 pub use error::{Error, Result};
-pub use modrinth::ModrinthPlatform;
+pub use metadata::mod_side;
+pub use modpack::{
+    CurseForgeInstall, CurseForgeManifest, CurseForgeManifestFile, CurseForgeMinecraft,
+    CurseForgeModLoader, CurseForgePack, EnvSupport, FileFailure, ManualDownload, Mrpack,
+    MrpackEnv, MrpackFile, MrpackHashes, MrpackIndex, PackSide, safe_join,
+};
+pub use modrinth::{
+    ModrinthFile, ModrinthHashes, ModrinthPlatform, ModrinthProject, ModrinthVersion,
+};
+use reqwest::Client;
+
+static HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
+
+pub(crate) fn http_client() -> &'static Client {
+    HTTP_CLIENT.get_or_init(|| {
+        Client::builder()
+            .user_agent("UNML/0.1.0")
+            .build()
+            .expect("Failed to create HTTP client")
+    })
+}
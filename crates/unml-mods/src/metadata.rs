@@ -0,0 +1,138 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{Error, PackSide, Result};
+
+const FABRIC_METADATA: &str = "fabric.mod.json";
+const QUILT_METADATA: &str = "quilt.mod.json";
+const FORGE_METADATA: &[&str] = &["META-INF/neoforge.mods.toml", "META-INF/mods.toml"];
+
+/// `side` 指向这些依赖时代表 Mod 本身只能运行在该侧
+const GAME_DEPENDENCIES: &[&str] = &["minecraft", "forge", "neoforge"];
+
+#[derive(Debug, Deserialize)]
+struct FabricMetadata {
+    #[serde(default)]
+    environment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QuiltMetadata {
+    #[serde(default)]
+    minecraft: Option<QuiltMinecraft>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QuiltMinecraft {
+    #[serde(default)]
+    environment: Option<String>,
+}
+
+/// 从 Mod jar 的元数据判断它只能运行在哪一侧，两侧都可用或无法判断时返回 `None`
+///
+/// 依次读取 `fabric.mod.json` 的 `environment`、`quilt.mod.json` 的
+/// `minecraft.environment`，以及 `mods.toml` 的 `displayTest`、`clientSideOnly`
+/// 与游戏依赖的 `side`。
+pub async fn mod_side(jar: &Path) -> Result<Option<PackSide>> {
+    let jar = jar.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        let mut zip = zip::ZipArchive::new(File::open(&jar)?)?;
+
+        if let Some(data) = read_optional(&mut zip, FABRIC_METADATA)? {
+            let metadata: FabricMetadata =
+                serde_json::from_slice(&data).map_err(unml_core::JsonError)?;
+            return Ok(environment_side(metadata.environment.as_deref()));
+        }
+
+        if let Some(data) = read_optional(&mut zip, QUILT_METADATA)? {
+            let metadata: QuiltMetadata =
+                serde_json::from_slice(&data).map_err(unml_core::JsonError)?;
+            let environment = metadata.minecraft.and_then(|m| m.environment);
+            return Ok(environment_side(environment.as_deref()));
+        }
+
+        for name in FORGE_METADATA {
+            if let Some(data) = read_optional(&mut zip, name)? {
+                return Ok(mods_toml_side(&String::from_utf8_lossy(&data)));
+            }
+        }
+
+        Ok(None)
+    })
+    .await
+    .map_err(|e| Error::InvalidModpack(e.to_string()))?
+}
+
+fn read_optional(zip: &mut zip::ZipArchive<File>, name: &str) -> Result<Option<Vec<u8>>> {
+    let mut entry = match zip.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data)?;
+
+    Ok(Some(data))
+}
+
+fn environment_side(environment: Option<&str>) -> Option<PackSide> {
+    match environment? {
+        "client" => Some(PackSide::Client),
+        "server" | "dedicated_server" => Some(PackSide::Server),
+        _ => None,
+    }
+}
+
+/// 逐行扫描 `mods.toml`，只关心几个键，不需要完整的 TOML 解析
+fn mods_toml_side(content: &str) -> Option<PackSide> {
+    let mut side = None;
+    let mut in_dependency = false;
+    let mut dependency: (Option<String>, Option<String>) = (None, None);
+
+    let mut finish_dependency = |dependency: &mut (Option<String>, Option<String>)| {
+        if let (Some(mod_id), Some(dep_side)) = std::mem::take(dependency)
+            && GAME_DEPENDENCIES.contains(&mod_id.as_str())
+        {
+            match dep_side.to_ascii_uppercase().as_str() {
+                "CLIENT" => side = side.or(Some(PackSide::Client)),
+                "SERVER" => side = side.or(Some(PackSide::Server)),
+                _ => {}
+            }
+        }
+    };
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            finish_dependency(&mut dependency);
+            in_dependency = line.starts_with("[[dependencies.");
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_matches(['"', '\'']);
+        match (key.trim(), in_dependency) {
+            ("displayTest", false) if value == "IGNORE_ALL_VERSION" => {
+                return Some(PackSide::Client);
+            }
+            ("clientSideOnly", false) if value == "true" => return Some(PackSide::Client),
+            ("modId", true) => dependency.0 = Some(value.to_owned()),
+            ("side", true) => dependency.1 = Some(value.to_owned()),
+            _ => {}
+        }
+    }
+    finish_dependency(&mut dependency);
+
+    side
+}
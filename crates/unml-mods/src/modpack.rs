@@ -0,0 +1,197 @@
+mod curseforge;
+mod mrpack;
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+pub use curseforge::{
+    CurseForgeInstall, CurseForgeManifest, CurseForgeManifestFile, CurseForgeMinecraft,
+    CurseForgeModLoader, CurseForgePack, ManualDownload,
+};
+use futures::StreamExt;
+pub use mrpack::{EnvSupport, Mrpack, MrpackEnv, MrpackFile, MrpackHashes, MrpackIndex};
+use sha1::{Digest, Sha1};
+use sha2::Sha512;
+use tokio::io::AsyncWriteExt;
+use unml_core::file_sha1;
+
+use crate::{Error, Result, http_client};
+
+/// 同时下载的文件数
+pub(crate) const MAX_CONCURRENT_DOWNLOADS: usize = 6;
+
+/// 安装整合包的一侧
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackSide {
+    Client,
+    Server,
+}
+
+/// 单个文件处理失败，不影响其他文件
+#[derive(Debug, Clone)]
+pub struct FileFailure {
+    /// 相对于游戏目录的路径
+    pub path: String,
+    pub error: String,
+}
+
+/// 把整合包中的相对路径拼接到目标目录，拒绝绝对路径和 `..`
+pub fn safe_join(root: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    Some(root.join(relative))
+}
+
+/// 读取压缩包中的单个文件
+pub(crate) async fn read_entry(archive: &Path, name: &str) -> Result<Vec<u8>> {
+    let archive = archive.to_path_buf();
+    let name = name.to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        let mut zip = zip::ZipArchive::new(File::open(&archive)?)?;
+        let mut entry = zip
+            .by_name(&name)
+            .map_err(|_| Error::InvalidModpack(format!("missing {name}")))?;
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+
+        Ok(data)
+    })
+    .await
+    .map_err(|e| Error::InvalidModpack(e.to_string()))?
+}
+
+/// 把压缩包中 `prefix` 目录下的内容解压到 `dest`，返回解压出的相对路径
+pub(crate) async fn extract_prefix(
+    archive: &Path,
+    prefix: &str,
+    dest: &Path,
+) -> Result<Vec<String>> {
+    let archive = archive.to_path_buf();
+    let prefix = prefix.trim_end_matches('/').to_owned();
+    let dest = dest.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        let mut zip = zip::ZipArchive::new(File::open(&archive)?)?;
+        let mut extracted = Vec::new();
+
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            let Some(name) = entry.enclosed_name() else {
+                continue;
+            };
+            let Ok(relative) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+
+            let target = dest.join(relative);
+            if entry.is_dir() {
+                std::fs::create_dir_all(&target)?;
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = File::create(&target)?;
+            std::io::copy(&mut entry, &mut file)?;
+            extracted.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+
+        Ok(extracted)
+    })
+    .await
+    .map_err(|e| Error::InvalidModpack(e.to_string()))?
+}
+
+/// 依次尝试各个下载地址，校验 SHA-1 与 SHA-512（如果提供）
+///
+/// 目标文件已存在且 SHA-1 一致时跳过下载。
+pub(crate) async fn download_file(
+    urls: &[String],
+    dest: &Path,
+    sha1: Option<&str>,
+    sha512: Option<&str>,
+) -> Result<()> {
+    if let Some(expected) = sha1
+        && file_sha1(dest).await.as_deref() == Some(expected)
+    {
+        return Ok(());
+    }
+
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut last_error = None;
+    for url in urls {
+        match download_verified(url, dest, sha1, sha512).await {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| Error::DownloadFailed(dest.display().to_string())))
+}
+
+async fn download_verified(
+    url: &str,
+    dest: &Path,
+    sha1: Option<&str>,
+    sha512: Option<&str>,
+) -> Result<()> {
+    let response = http_client()
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+    // 追加到完整文件名，避免 `foo.jar` 与 `foo.zip` 并发下载时冲突
+    let mut tmp = dest.as_os_str().to_owned();
+    tmp.push(".part");
+    let tmp = PathBuf::from(tmp);
+    let mut file = tokio::fs::File::create(&tmp).await?;
+    let mut sha1_hasher = Sha1::new();
+    let mut sha512_hasher = Sha512::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| unml_core::HttpError(e.to_string()))?;
+        sha1_hasher.update(&chunk);
+        sha512_hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    drop(file);
+
+    let checks = [
+        (sha1, format!("{:x}", sha1_hasher.finalize())),
+        (sha512, format!("{:x}", sha512_hasher.finalize())),
+    ];
+    for (expected, actual) in checks {
+        if let Some(expected) = expected
+            && !actual.eq_ignore_ascii_case(expected)
+        {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(unml_core::ChecksumError {
+                expected: expected.to_owned(),
+                actual,
+            }
+            .into());
+        }
+    }
+
+    tokio::fs::rename(&tmp, dest).await?;
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use unml_core::{LoaderSpec, ModLoader, ProgressCallback};
+
+use super::{
+    FileFailure, MAX_CONCURRENT_DOWNLOADS, download_file, extract_prefix, read_entry, safe_join,
+};
+use crate::{CurseForgeMod, CurseForgePlatform, Error, Result};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// CurseForge 整合包的 `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    pub manifest_type: String,
+    pub manifest_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub author: String,
+    pub files: Vec<CurseForgeManifestFile>,
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_owned()
+}
+
+impl CurseForgeManifest {
+    /// [`Self::loader`] 未采用的加载器 ID，包括无法识别的与多余的加载器
+    pub fn skipped_loaders(&self) -> Vec<&str> {
+        let used = self.loader().and(self.primary_loader());
+        self.minecraft
+            .mod_loaders
+            .iter()
+            .filter(|loader| !used.is_some_and(|used| std::ptr::eq(used, *loader)))
+            .map(|loader| loader.id.as_str())
+            .collect()
+    }
+
+    fn primary_loader(&self) -> Option<&CurseForgeModLoader> {
+        self.minecraft
+            .mod_loaders
+            .iter()
+            .find(|loader| loader.primary)
+            .or_else(|| self.minecraft.mod_loaders.first())
+    }
+
+    /// 主加载器，形如 `forge-47.2.0`、`fabric-0.15.0`
+    pub fn loader(&self) -> Option<LoaderSpec> {
+        let (name, version) = self.primary_loader()?.id.split_once('-')?;
+
+        let loader = match name {
+            "forge" => ModLoader::Forge,
+            "neoforge" => ModLoader::NeoForge,
+            "fabric" => ModLoader::Fabric,
+            "quilt" => ModLoader::Quilt,
+            _ => return None,
+        };
+
+        Some(LoaderSpec {
+            loader,
+            version: version.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurseForgeModLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// 作者禁止第三方下载的文件，需要用户在网页上手动下载
+#[derive(Debug, Clone)]
+pub struct ManualDownload {
+    pub project_id: u64,
+    pub file_id: u64,
+    pub name: String,
+    pub file_name: String,
+    /// 下载后应放入的路径（相对于游戏目录）
+    pub path: String,
+    /// 文件页面
+    pub url: Option<String>,
+}
+
+/// CurseForge 文件的安装结果
+#[derive(Debug, Default)]
+pub struct CurseForgeInstall {
+    /// 成功下载的文件（相对于游戏目录）
+    pub installed: Vec<String>,
+    pub failed: Vec<FileFailure>,
+    pub manual: Vec<ManualDownload>,
+}
+
+/// 本地 CurseForge 整合包
+pub struct CurseForgePack {
+    path: PathBuf,
+    manifest: CurseForgeManifest,
+}
+
+impl CurseForgePack {
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let data = read_entry(&path, MANIFEST_FILE).await?;
+        let manifest: CurseForgeManifest =
+            serde_json::from_slice(&data).map_err(unml_core::JsonError)?;
+
+        if manifest.manifest_type != "minecraftModpack" {
+            return Err(Error::InvalidModpack(format!(
+                "unsupported manifest type: {}",
+                manifest.manifest_type
+            )));
+        }
+
+        Ok(Self { path, manifest })
+    }
+
+    pub fn manifest(&self) -> &CurseForgeManifest {
+        &self.manifest
+    }
+
+    pub async fn extract_overrides(&self, dest: &Path) -> Result<Vec<String>> {
+        extract_prefix(&self.path, &self.manifest.overrides, dest).await
+    }
+
+    /// 通过 CurseForge API 批量解析并下载清单中的文件
+    ///
+    /// `progress` 报告已处理的文件数与总数。
+    pub async fn install_files(
+        &self,
+        platform: &CurseForgePlatform,
+        dest: &Path,
+        progress: Option<ProgressCallback>,
+    ) -> Result<CurseForgeInstall> {
+        let entries: Vec<&CurseForgeManifestFile> =
+            self.manifest.files.iter().filter(|f| f.required).collect();
+
+        let file_ids: Vec<u64> = entries.iter().map(|f| f.file_id).collect();
+        let project_ids: Vec<u64> = entries.iter().map(|f| f.project_id).collect();
+        let files = platform.get_files(&file_ids).await?;
+        let projects: HashMap<u64, CurseForgeMod> = platform
+            .get_mods(&project_ids)
+            .await?
+            .into_iter()
+            .map(|project| (project.id, project))
+            .collect();
+
+        let mut install = CurseForgeInstall::default();
+        let resolved: HashMap<u64, _> = files.into_iter().map(|file| (file.id, file)).collect();
+
+        let mut downloads = Vec::new();
+        for entry in entries {
+            let Some(file) = resolved.get(&entry.file_id) else {
+                install.failed.push(FileFailure {
+                    path: format!("{}/{}", entry.project_id, entry.file_id),
+                    error: "file not found on CurseForge".to_owned(),
+                });
+                continue;
+            };
+
+            let project = projects.get(&entry.project_id);
+            let dir = project.map_or("mods", CurseForgeMod::install_dir);
+            let path = format!("{dir}/{}", file.file_name);
+            // 文件名来自 API，同样不能写出游戏目录
+            let target = match safe_join(dest, &path) {
+                Some(target) if !file.file_name.contains(['/', '\\']) => target,
+                _ => {
+                    install.failed.push(FileFailure {
+                        path,
+                        error: "unsafe file name".to_owned(),
+                    });
+                    continue;
+                }
+            };
+
+            match &file.download_url {
+                Some(url) => {
+                    downloads.push((path, target, url.clone(), file.sha1().map(str::to_owned)))
+                }
+                None => install.manual.push(ManualDownload {
+                    project_id: entry.project_id,
+                    file_id: entry.file_id,
+                    name: project.map_or_else(|| file.display_name.clone(), |p| p.name.clone()),
+                    file_name: file.file_name.clone(),
+                    path,
+                    url: project
+                        .and_then(|p| p.links.website_url.as_ref())
+                        .map(|url| format!("{url}/files/{}", entry.file_id)),
+                }),
+            }
+        }
+
+        let paths: Vec<String> = downloads.iter().map(|(path, ..)| path.clone()).collect();
+        let total = downloads.len() as u64;
+        let done = Arc::new(AtomicU64::new(0));
+        let progress = progress.map(Arc::new);
+
+        let failed: Vec<FileFailure> = futures::stream::iter(downloads)
+            .map(|(path, target, url, sha1)| {
+                let done = Arc::clone(&done);
+                let progress = progress.clone();
+                async move {
+                    let result = download_file(&[url], &target, sha1.as_deref(), None).await;
+
+                    let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(progress) = &progress {
+                        progress(done, total);
+                    }
+
+                    result.err().map(|e| FileFailure {
+                        path,
+                        error: e.to_string(),
+                    })
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_DOWNLOADS)
+            .filter_map(futures::future::ready)
+            .collect()
+            .await;
+        install.installed = paths
+            .into_iter()
+            .filter(|path| !failed.iter().any(|f| &f.path == path))
+            .collect();
+        install.failed.extend(failed);
+
+        Ok(install)
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use unml_core::{LoaderSpec, ModLoader, ProgressCallback};
+
+use super::{
+    FileFailure, MAX_CONCURRENT_DOWNLOADS, PackSide, download_file, extract_prefix, read_entry,
+    safe_join,
+};
+use crate::{Error, Result};
+
+const INDEX_FILE: &str = "modrinth.index.json";
+/// `.mrpack` 规范允许的下载域名
+const ALLOWED_DOWNLOAD_HOSTS: &[&str] = &[
+    "cdn.modrinth.com",
+    "github.com",
+    "raw.githubusercontent.com",
+    "gitlab.com",
+];
+
+/// `modrinth.index.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackIndex {
+    pub format_version: u32,
+    pub game: String,
+    pub version_id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub files: Vec<MrpackFile>,
+    /// `minecraft`、`forge`、`neoforge`、`fabric-loader`、`quilt-loader`
+    /// 到版本号
+    pub dependencies: HashMap<String, String>,
+}
+
+impl MrpackIndex {
+    pub fn game_version(&self) -> Option<&str> {
+        self.dependencies.get("minecraft").map(String::as_str)
+    }
+
+    pub fn loader(&self) -> Option<LoaderSpec> {
+        [
+            ("forge", ModLoader::Forge),
+            ("neoforge", ModLoader::NeoForge),
+            ("fabric-loader", ModLoader::Fabric),
+            ("quilt-loader", ModLoader::Quilt),
+        ]
+        .into_iter()
+        .find_map(|(key, loader)| {
+            self.dependencies.get(key).map(|version| LoaderSpec {
+                loader,
+                version: version.clone(),
+            })
+        })
+    }
+
+    /// 目标一侧需要安装的文件，可选文件默认安装
+    pub fn files_for(&self, side: PackSide) -> impl Iterator<Item = &MrpackFile> {
+        self.files.iter().filter(move |file| file.supports(side))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackFile {
+    pub path: String,
+    pub hashes: MrpackHashes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrpackEnv>,
+    pub downloads: Vec<String>,
+    pub file_size: u64,
+}
+
+impl MrpackFile {
+    /// 位于规范允许域名下的 HTTPS 下载地址
+    pub fn allowed_downloads(&self) -> Vec<String> {
+        self.downloads
+            .iter()
+            .filter(|url| {
+                reqwest::Url::parse(url).is_ok_and(|url| {
+                    url.scheme() == "https"
+                        && url
+                            .host_str()
+                            .is_some_and(|host| ALLOWED_DOWNLOAD_HOSTS.contains(&host))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn supports(&self, side: PackSide) -> bool {
+        let Some(env) = &self.env else {
+            return true;
+        };
+
+        match side {
+            PackSide::Client => env.client != EnvSupport::Unsupported,
+            PackSide::Server => env.server != EnvSupport::Unsupported,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrpackHashes {
+    pub sha1: String,
+    pub sha512: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrpackEnv {
+    pub client: EnvSupport,
+    pub server: EnvSupport,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvSupport {
+    Required,
+    Optional,
+    Unsupported,
+    /// Modrinth 项目未标注
+    #[serde(other)]
+    Unknown,
+}
+
+/// 本地 `.mrpack` 文件
+pub struct Mrpack {
+    path: PathBuf,
+    index: MrpackIndex,
+}
+
+impl Mrpack {
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let data = read_entry(&path, INDEX_FILE).await?;
+        let index: MrpackIndex = serde_json::from_slice(&data).map_err(unml_core::JsonError)?;
+
+        if index.game != "minecraft" {
+            return Err(Error::InvalidModpack(format!(
+                "unsupported game: {}",
+                index.game
+            )));
+        }
+
+        Ok(Self { path, index })
+    }
+
+    pub fn index(&self) -> &MrpackIndex {
+        &self.index
+    }
+
+    /// 解压 `overrides/`，再用 `client-overrides/` 或 `server-overrides/` 覆盖
+    pub async fn extract_overrides(&self, dest: &Path, side: PackSide) -> Result<Vec<String>> {
+        let mut extracted = extract_prefix(&self.path, "overrides", dest).await?;
+
+        let side_prefix = match side {
+            PackSide::Client => "client-overrides",
+            PackSide::Server => "server-overrides",
+        };
+        extracted.extend(extract_prefix(&self.path, side_prefix, dest).await?);
+
+        Ok(extracted)
+    }
+
+    /// 下载目标一侧的所有文件，`progress` 报告已完成的文件数与总数
+    ///
+    /// 单个文件失败不会中断安装，失败的文件在返回值中列出。
+    pub async fn download_files(
+        &self,
+        dest: &Path,
+        side: PackSide,
+        progress: Option<ProgressCallback>,
+    ) -> Vec<FileFailure> {
+        let files: Vec<&MrpackFile> = self.index.files_for(side).collect();
+        let total = files.len() as u64;
+        let done = Arc::new(AtomicU64::new(0));
+        let progress = progress.map(Arc::new);
+
+        futures::stream::iter(files)
+            .map(|file| {
+                let done = Arc::clone(&done);
+                let progress = progress.clone();
+                async move {
+                    let urls = file.allowed_downloads();
+                    let result = match safe_join(dest, &file.path) {
+                        None => Err(Error::InvalidModpack(format!("unsafe path: {}", file.path))),
+                        Some(_) if urls.is_empty() => Err(Error::InvalidModpack(format!(
+                            "no download from an allowed domain: {}",
+                            file.path
+                        ))),
+                        Some(target) => {
+                            download_file(
+                                &urls,
+                                &target,
+                                Some(&file.hashes.sha1),
+                                Some(&file.hashes.sha512),
+                            )
+                            .await
+                        }
+                    };
+
+                    let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(progress) = &progress {
+                        progress(done, total);
+                    }
+
+                    result.err().map(|e| FileFailure {
+                        path: file.path.clone(),
+                        error: e.to_string(),
+                    })
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_DOWNLOADS)
+            .filter_map(futures::future::ready)
+            .collect()
+            .await
+    }
+}
@@ -1,16 +1,116 @@
+use std::collections::HashMap;
 use std::path::Path;
 
 use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use unml_core::{ModDetail, ModInfo, ModPlatform, ModVersion, ProgressCallback, SearchFilters};
 
-use crate::{Error, Result};
+use crate::{EnvSupport, Error, Result, http_client};
 
-pub struct ModrinthPlatform;
+/// Modrinth 上的版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    #[serde(default)]
+    pub version_number: String,
+    pub files: Vec<ModrinthFile>,
+}
+
+impl ModrinthVersion {
+    /// 按 SHA-1 查找版本中的文件
+    pub fn file_by_sha1(&self, sha1: &str) -> Option<&ModrinthFile> {
+        self.files
+            .iter()
+            .find(|file| file.hashes.sha1.eq_ignore_ascii_case(sha1))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthFile {
+    pub hashes: ModrinthHashes,
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthHashes {
+    pub sha1: String,
+    pub sha512: String,
+}
+
+/// Modrinth 上的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthProject {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub client_side: EnvSupport,
+    pub server_side: EnvSupport,
+}
+
+pub struct ModrinthPlatform {
+    base_url: String,
+}
 
 impl ModrinthPlatform {
     pub fn new() -> Self {
-        Self
+        Self {
+            base_url: "https://api.modrinth.com".to_owned(),
+        }
     }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// 按 SHA-1 批量识别文件，返回哈希到版本的映射，未识别的哈希不在结果中
+    pub async fn get_versions_by_sha1(
+        &self,
+        hashes: &[String],
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let request = http_client()
+            .post(format!("{}/v2/version_files", self.base_url))
+            .json(&serde_json::json!({ "hashes": hashes, "algorithm": "sha1" }));
+        fetch(request).await
+    }
+
+    /// 批量获取项目信息
+    pub async fn get_projects(&self, ids: &[String]) -> Result<Vec<ModrinthProject>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids = serde_json::to_string(ids).map_err(unml_core::JsonError)?;
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/v2/projects", self.base_url),
+            &[("ids", ids)],
+        )
+        .map_err(|e| unml_core::HttpError(e.to_string()))?;
+        fetch(http_client().get(url)).await
+    }
+}
+
+async fn fetch<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T> {
+    let bytes = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| unml_core::HttpError(e.to_string()))?
+        .bytes()
+        .await
+        .map_err(|e| unml_core::HttpError(e.to_string()))?;
+
+    Ok(serde_json::from_slice(&bytes).map_err(unml_core::JsonError)?)
 }
 
 #[async_trait]